@@ -0,0 +1,85 @@
+//! Writes the `cargo test` cases for the actual inputs of every day with an answers file, `harness::days!` includes them
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Same as `harness::DEFAULT_YEAR`
+const DEFAULT_YEAR: u16 = 2024;
+
+const ACTUAL_TESTS: &str = "\
+#[test]
+fn part1_actual() {
+    check(1, Kind::Actual);
+}
+
+#[test]
+fn part2_actual() {
+    check(2, Kind::Actual);
+}
+";
+
+fn main() {
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR isn't set"));
+
+    println!("cargo:rerun-if-changed=build.rs");
+    // Watching a missing path would rerun this on every build, so new inputs are only noticed once input/ exists
+    if Path::new("input").is_dir() {
+        println!("cargo:rerun-if-changed=input");
+    }
+
+    write_tests(&out_dir, "", &registered("src/lib.rs", "day"), &input_dir(DEFAULT_YEAR));
+
+    for module in registered("src/lib.rs", "mod y") {
+        let year = module.parse::<u16>().expect("Year modules are named yYYYY");
+        let days = registered(&format!("src/y{}/mod.rs", year), "day");
+
+        write_tests(&out_dir, &format!("{}_", year), &days, &input_dir(year));
+    }
+}
+
+/// The entries of the `days!` registry in the file that start with the prefix, without the prefix
+fn registered(file: &str, prefix: &str) -> Vec<String> {
+    println!("cargo:rerun-if-changed={}", file);
+
+    fs::read_to_string(file)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", file, e))
+        .lines()
+        .filter_map(|e| e.trim().strip_prefix(prefix))
+        .map(|e| e.trim_end_matches([',', ';']).to_string())
+        .collect()
+}
+
+/// Like `harness::input_dir`
+fn input_dir(year: u16) -> PathBuf {
+    let dir = Path::new("input").join(year.to_string());
+
+    if year == DEFAULT_YEAR && !dir.is_dir() && has_flat_inputs(Path::new("input")) {
+        PathBuf::from("input")
+    } else {
+        dir
+    }
+}
+
+/// Whether the directory holds inputs like `01.txt` or `01_test.txt` itself rather than in year directories
+fn has_flat_inputs(dir: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+
+    entries.filter_map(Result::ok).any(|e| {
+        let name = e.file_name();
+        let name = name.to_string_lossy();
+        name.len() > 2 && name.as_bytes()[..2].iter().all(u8::is_ascii_digit) && name.ends_with(".txt")
+    })
+}
+
+fn write_tests(out_dir: &Path, prefix: &str, days: &[String], input_dir: &Path) {
+    for id in days {
+        let answers = input_dir.join(format!("{}_answers.txt", id));
+        let tests = if answers.is_file() { ACTUAL_TESTS } else { "" };
+        let path = out_dir.join(format!("actual_{}day{}.rs", prefix, id));
+
+        fs::write(&path, tests).unwrap_or_else(|e| panic!("Failed to write {}: {}", path.display(), e));
+    }
+}
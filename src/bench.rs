@@ -0,0 +1,151 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 10,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    pub low_outliers: usize,
+    pub high_outliers: usize,
+    pub samples: usize,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "Can't compute stats without samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let nanos = sorted.iter().map(|e| e.as_nanos() as f64).collect::<Vec<_>>();
+        let n = nanos.len() as f64;
+
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = nanos.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / n;
+
+        // Tukey's fences
+        let q1 = quantile(&nanos, 0.25);
+        let q3 = quantile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let low_fence = q1 - 1.5 * iqr;
+        let high_fence = q3 + 1.5 * iqr;
+
+        Self {
+            min: sorted[0],
+            median: from_nanos(quantile(&nanos, 0.5)),
+            mean: from_nanos(mean),
+            p95: sorted[((n * 0.95).ceil() as usize).clamp(1, sorted.len()) - 1],
+            stddev: from_nanos(variance.sqrt()),
+            low_outliers: nanos.iter().filter(|&&e| e < low_fence).count(),
+            high_outliers: nanos.iter().filter(|&&e| e > high_fence).count(),
+            samples: sorted.len(),
+        }
+    }
+
+    pub fn outliers(&self) -> usize {
+        self.low_outliers + self.high_outliers
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:?} median {:?} mean {:?} p95 {:?} σ {:?} outliers {}/{} ({} low, {} high)",
+            self.min,
+            self.median,
+            self.mean,
+            self.p95,
+            self.stddev,
+            self.outliers(),
+            self.samples,
+            self.low_outliers,
+            self.high_outliers,
+        )
+    }
+}
+
+/// Linearly interpolated quantile of already sorted values
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = (sorted.len() - 1) as f64 * q;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&e| Duration::from_micros(e)).collect()
+    }
+
+    #[test]
+    fn quantile_interpolates_between_samples() {
+        assert_eq!(quantile(&[1.0, 2.0, 3.0], 0.5), 2.0);
+        assert_eq!(quantile(&[1.0, 2.0, 3.0, 4.0], 0.5), 2.5);
+        assert_eq!(quantile(&[1.0, 2.0, 3.0, 4.0, 5.0], 0.25), 2.0);
+        assert_eq!(quantile(&[10.0, 20.0], 0.75), 17.5);
+        assert_eq!(quantile(&[7.0], 0.95), 7.0);
+    }
+
+    #[test]
+    fn stats_of_an_odd_count() {
+        let stats = Stats::new(&micros(&[5, 1, 3, 2, 4]));
+
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.stddev, Duration::from_nanos(1414));
+        assert_eq!((stats.outliers(), stats.samples), (0, 5));
+    }
+
+    #[test]
+    fn stats_of_an_even_count() {
+        let stats = Stats::new(&micros(&[4, 1, 3, 2]));
+
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        assert_eq!(stats.p95, Duration::from_micros(4));
+        assert_eq!(stats.outliers(), 0);
+    }
+
+    #[test]
+    fn stats_count_outliers_outside_the_fences() {
+        // q1 is 11.25 and q3 13, so the fences are at 8.625 and 15.625
+        let stats = Stats::new(&micros(&[10, 11, 11, 12, 12, 12, 13, 13, 14, 100]));
+
+        assert_eq!(stats.median, Duration::from_micros(12));
+        assert_eq!(stats.p95, Duration::from_micros(100));
+        assert_eq!((stats.low_outliers, stats.high_outliers), (0, 1));
+
+        let stats = Stats::new(&micros(&[1, 50, 50, 51, 51, 52, 52]));
+
+        assert_eq!((stats.low_outliers, stats.high_outliers), (1, 0));
+    }
+}
@@ -0,0 +1,435 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_2024::bench::BenchOptions;
+use crate::client::ClientOptions;
+use aoc_2024::harness::RunOptions;
+use aoc_2024::results::Format;
+
+pub const USAGE: &str = "\
+Usage: aoc-2024 [run] [all | <days>] [options]
+       aoc-2024 fetch [all | <days>] [options]
+       aoc-2024 submit <day> <part> [options]
+       aoc-2024 list
+       aoc-2024 new-day <day>
+       aoc-2024 watch <day> [options]
+
+Commands:
+  fetch            Download the inputs of the days into input/, inputs
+                   that are already there are never downloaded again
+  submit           Run a part and submit its answer, answers that are
+                   known to be wrong from input/submissions.csv are
+                   never submitted again
+  list             List the available days
+  new-day          Create src/dayNN.rs from a template with empty inputs
+                   and register it, existing files are never overwritten,
+                   days of other years go into src/yYYYY/dayNN.rs
+  watch            Rebuild and run the day whenever its source or inputs
+                   change and compare the answers and timings to the
+                   previous run
+
+Days:
+  <days>           Comma separated days and ranges, e.g. 3-7,12
+  all              Run every day
+                   Without any days, the latest day is run
+
+Options:
+  --year <yyyy>    Run the days of this year (default the latest year)
+  --part <1|2>     Only run the given part
+  --test-only      Only run the test inputs
+  --actual-only    Only run the actual inputs
+  --bench          Benchmark the actual inputs and report timing statistics
+  --warmup <n>     Number of unmeasured warmup runs per part (default 3)
+  --iterations <n> Number of measured runs per part (default 10)
+  --input <file>   Run the parts on this file instead of the actual input,
+                   the tests and the known answers are skipped
+  --stdin          Run the parts on the standard input, like --input
+  --params <p>     Parameters for --input and --stdin, test for those of
+                   the examples, e.g. a grid size (default actual)
+  --expect <value> Expected answer of the actual input, needs --part
+  --memory         Count the allocations, total and peak bytes of each part
+  --timeout <s>    Give up on parts that take longer than s seconds
+  -j, --jobs <n>   Run days on n threads in parallel, timings are less
+                   reliable than when running sequentially (default 1)
+  --format <f>     Output format, plain, json or csv with one record per
+                   part execution (default plain)
+  --plot <file>    Plot the timings to an SVG file, or a PNG file if its
+                   name ends in .png
+  --log            Use a logarithmic scale for the plot
+  --save <file>    Write the results to a CSV file
+  --compare <file> Compare the results to a previously saved baseline and
+                   exit with an error if any part got slower
+  --threshold <p>  Allowed slowdown in percent for --compare (default 10)
+  -h, --help       Print this help
+
+Fetch and submit options:
+  --base-url <url> Puzzle server (default https://adventofcode.com)
+  --session <file> File with the session token, used if AOC_SESSION
+                   isn't set (default .session)
+  --interval <s>   Minimum seconds between requests (default 5)";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run,
+    Fetch,
+    Submit,
+    List,
+    NewDay,
+    Watch,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Days {
+    Latest,
+    All,
+    Selected(Vec<usize>),
+}
+
+#[derive(Debug)]
+pub struct Args {
+    pub command: Command,
+    pub days: Days,
+    pub options: RunOptions,
+    pub year: Option<u16>,
+    pub jobs: usize,
+    pub memory: bool,
+    pub log_scale: bool,
+    pub plot: Option<PathBuf>,
+    pub format: Format,
+    pub save: Option<PathBuf>,
+    pub compare: Option<PathBuf>,
+    pub threshold: f64,
+    pub input: Option<PathBuf>,
+    pub stdin: bool,
+    pub client: ClientOptions,
+    pub help: bool,
+}
+
+pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Args, String> {
+    let mut command = Command::Run;
+    let mut days = None;
+    let mut options = RunOptions::default();
+    let mut year = None;
+    let mut jobs = 1;
+    let mut memory = false;
+    let mut log_scale = false;
+    let mut plot = None;
+    let mut format = Format::Plain;
+    let mut save = None;
+    let mut compare = None;
+    let mut threshold = 10.0;
+    let mut input = None;
+    let mut stdin = false;
+    let mut client = ClientOptions::default();
+    let mut help = false;
+    let mut test_only = false;
+    let mut actual_only = false;
+    let mut bench = false;
+    let mut bench_options = BenchOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => help = true,
+            "--log" => log_scale = true,
+            "--test-only" => test_only = true,
+            "--actual-only" => actual_only = true,
+            "--bench" => bench = true,
+            "--stdin" => stdin = true,
+            "--memory" => memory = true,
+            "--input" => {
+                input = Some(PathBuf::from(args.next().ok_or("--input requires a file")?));
+            }
+            "--params" => {
+                let value = args.next().ok_or("--params requires a value")?;
+                options.test_params = match value.as_str() {
+                    "test" => true,
+                    "actual" => false,
+                    _ => return Err(format!("Invalid params '{}', expected test or actual", value)),
+                };
+            }
+            "--expect" => {
+                options.expect = Some(args.next().ok_or("--expect requires a value")?);
+            }
+            "--warmup" => {
+                let value = args.next().ok_or("--warmup requires a value")?;
+                bench_options.warmup = parse_count(&value, 0)?;
+            }
+            "--iterations" => {
+                let value = args.next().ok_or("--iterations requires a value")?;
+                bench_options.iterations = parse_count(&value, 1)?;
+            }
+            "--timeout" => {
+                let value = args.next().ok_or("--timeout requires a value")?;
+                options.timeout = Some(parse_seconds(&value, false)?);
+            }
+            "--year" => {
+                let value = args.next().ok_or("--year requires a value")?;
+                year = Some(
+                    value
+                        .parse::<u16>()
+                        .ok()
+                        .filter(|&e| e >= 2015)
+                        .ok_or_else(|| format!("Invalid year '{}'", value))?,
+                );
+            }
+            "-j" | "--jobs" => {
+                let value = args.next().ok_or("--jobs requires a value")?;
+                jobs = parse_count(&value, 1)?;
+            }
+            "--format" => {
+                let value = args.next().ok_or("--format requires a value")?;
+                format = match value.as_str() {
+                    "plain" => Format::Plain,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    _ => return Err(format!("Invalid format '{}', expected plain, json or csv", value)),
+                };
+            }
+            "--plot" => {
+                plot = Some(PathBuf::from(args.next().ok_or("--plot requires a file")?));
+            }
+            "--save" => {
+                save = Some(PathBuf::from(args.next().ok_or("--save requires a file")?));
+            }
+            "--compare" => {
+                compare = Some(PathBuf::from(args.next().ok_or("--compare requires a file")?));
+            }
+            "--threshold" => {
+                let value = args.next().ok_or("--threshold requires a value")?;
+                threshold = value
+                    .trim_end_matches('%')
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|&e| e >= 0.0)
+                    .ok_or_else(|| format!("Invalid threshold '{}'", value))?;
+            }
+            "--base-url" => {
+                client.base_url = args.next().ok_or("--base-url requires a value")?;
+            }
+            "--session" => {
+                client.session_file = PathBuf::from(args.next().ok_or("--session requires a file")?);
+            }
+            "--interval" => {
+                let value = args.next().ok_or("--interval requires a value")?;
+                client.interval = parse_seconds(&value, true)?;
+            }
+            "--part" => {
+                let value = args.next().ok_or("--part requires a value")?;
+                options.part = Some(parse_part(&value)?);
+            }
+            _ if arg.starts_with("--part=") => {
+                options.part = Some(parse_part(&arg["--part=".len()..])?);
+            }
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{}'", arg)),
+            "new-day" if command == Command::Run && days.is_none() => {
+                let day = parse_day(&args.next().ok_or("new-day requires a day")?)?;
+                days = Some(Days::Selected(vec![day]));
+                command = Command::NewDay;
+            }
+            "watch" if command == Command::Run && days.is_none() => {
+                let day = parse_day(&args.next().ok_or("watch requires a day")?)?;
+                days = Some(Days::Selected(vec![day]));
+                command = Command::Watch;
+            }
+            "run" if command == Command::Run && days.is_none() => {}
+            "list" if command == Command::Run && days.is_none() => command = Command::List,
+            "fetch" if command == Command::Run && days.is_none() => command = Command::Fetch,
+            "submit" if command == Command::Run && days.is_none() => {
+                let day = args.next().ok_or("submit requires a day and a part")?;
+                let part = args.next().ok_or("submit requires a day and a part")?;
+                days = Some(Days::Selected(vec![parse_day(&day)?]));
+                options.part = Some(parse_part(&part)?);
+                command = Command::Submit;
+            }
+            _ => {
+                if days.is_some() {
+                    return Err(format!("Unexpected argument '{}'", arg));
+                }
+
+                days = Some(if arg == "all" {
+                    Days::All
+                } else {
+                    Days::Selected(parse_days(&arg)?)
+                });
+            }
+        }
+    }
+
+    if test_only && actual_only {
+        return Err("--test-only and --actual-only are mutually exclusive".to_string());
+    }
+
+    if input.is_some() && stdin {
+        return Err("--input and --stdin are mutually exclusive".to_string());
+    }
+
+    if (input.is_some() || stdin) && test_only {
+        return Err("--test-only can't be combined with --input or --stdin".to_string());
+    }
+
+    if options.test_params && input.is_none() && !stdin {
+        return Err("--params requires --input or --stdin".to_string());
+    }
+
+    if (input.is_some() || stdin) && command != Command::Run {
+        return Err("--input and --stdin can only be used to run days".to_string());
+    }
+
+    if format != Format::Plain && compare.is_some() {
+        return Err("--compare only works with --format plain".to_string());
+    }
+
+    if log_scale && plot.is_none() {
+        return Err("--log requires --plot".to_string());
+    }
+
+    if options.expect.is_some() && options.part.is_none() {
+        return Err("--expect requires --part".to_string());
+    }
+
+    options.test = !actual_only;
+    options.actual = !test_only;
+    options.bench = bench.then_some(bench_options);
+
+    Ok(Args {
+        command,
+        days: days.unwrap_or(Days::Latest),
+        options,
+        year,
+        jobs,
+        memory,
+        log_scale,
+        plot,
+        format,
+        save,
+        compare,
+        threshold,
+        input,
+        stdin,
+        client,
+        help,
+    })
+}
+
+fn parse_part(value: &str) -> Result<u8, String> {
+    match value {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("Invalid part '{}', expected 1 or 2", value)),
+    }
+}
+
+fn parse_count(value: &str, min: usize) -> Result<usize, String> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|&e| e >= min)
+        .ok_or_else(|| format!("Invalid count '{}', expected a number >= {}", value, min))
+}
+
+fn parse_seconds(value: &str, allow_zero: bool) -> Result<Duration, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|&e| e > 0.0 || (allow_zero && e == 0.0))
+        .and_then(|e| Duration::try_from_secs_f64(e).ok())
+        .ok_or_else(|| format!("Invalid number of seconds '{}'", value))
+}
+
+/// The opposite of `parse_days`, e.g. 3-7,12
+pub fn format_days(ids: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = vec![];
+
+    for &id in ids {
+        match ranges.last_mut() {
+            Some((_, to)) if *to + 1 == id => *to = id,
+            _ => ranges.push((id, id)),
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|&(from, to)| if from == to { from.to_string() } else { format!("{}-{}", from, to) })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Days are checked here, so ranges never expand to more than the days of a year
+fn parse_day(value: &str) -> Result<usize, String> {
+    value
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|e| (1..=25).contains(e))
+        .ok_or_else(|| format!("Invalid day '{}', expected a day between 1 and 25", value))
+}
+
+fn parse_days(value: &str) -> Result<Vec<usize>, String> {
+    let mut result = vec![];
+
+    for item in value.split(',') {
+        match item.split_once('-') {
+            Some((from, to)) => {
+                let from = parse_day(from)?;
+                let to = parse_day(to)?;
+
+                if from > to {
+                    return Err(format!("Invalid day range '{}'", item));
+                }
+
+                result.extend(from..=to);
+            }
+            None => result.push(parse_day(item)?),
+        }
+    }
+
+    result.sort();
+    result.dedup();
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_days_sorts_and_deduplicates() {
+        assert_eq!(parse_days("12,3-7,5"), Ok(vec![3, 4, 5, 6, 7, 12]));
+        assert_eq!(parse_days(" 1 - 2 ,25"), Ok(vec![1, 2, 25]));
+    }
+
+    #[test]
+    fn parse_days_rejects_days_outside_of_a_year() {
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("1-99999999999999").is_err());
+        assert!(parse_days("99999999999999999999").is_err());
+    }
+
+    #[test]
+    fn parse_days_rejects_malformed_days() {
+        assert!(parse_days("7-3").is_err());
+        assert!(parse_days("3-").is_err());
+        assert!(parse_days("a").is_err());
+        assert!(parse_days("1,,2").is_err());
+    }
+
+    #[test]
+    fn params_choose_the_test_parameters_for_custom_inputs() {
+        let args = |e: &str| parse(e.split(' ').map(String::from));
+
+        assert!(args("18 --input example.txt --params test").unwrap().options.test_params);
+        assert!(!args("18 --stdin --params actual").unwrap().options.test_params);
+        assert!(args("18 --params test").is_err());
+        assert!(args("18 --input example.txt --params example").is_err());
+    }
+
+    #[test]
+    fn format_days_is_the_opposite_of_parse_days() {
+        let days = vec![1, 2, 3, 5, 7, 8];
+
+        assert_eq!(format_days(&days), "1-3,5,7-8");
+        assert_eq!(parse_days(&format_days(&days)), Ok(days));
+    }
+}
@@ -0,0 +1,129 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+
+const SESSION_VAR: &str = "AOC_SESSION";
+const USER_AGENT: &str = "aoc-2024 puzzle harness via ureq";
+/// Time of the last request, so the rate limit also holds across runs. Not in `input/`, which would then look like it holds inputs.
+const LAST_REQUEST_FILE: &str = "target/.last_request";
+
+/// Where requests go and how often, the base URL can point at a local mock server
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    pub base_url: String,
+    pub session_file: PathBuf,
+    pub interval: Duration,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            base_url: "https://adventofcode.com".to_string(),
+            session_file: PathBuf::from(".session"),
+            interval: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Talks to an AoC compatible server, authenticated with the session cookie
+pub struct Client {
+    options: ClientOptions,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// Takes the session token from `AOC_SESSION`, or else from the session file
+    pub fn new(options: &ClientOptions) -> Result<Self, String> {
+        let session = match env::var(SESSION_VAR) {
+            Ok(session) => session,
+            Err(_) => fs::read_to_string(&options.session_file).map_err(|e| {
+                format!(
+                    "No session token, set {} or write it to {}: {}",
+                    SESSION_VAR,
+                    options.session_file.display(),
+                    e
+                )
+            })?,
+        };
+        let session = session.trim().trim_start_matches("session=").to_string();
+
+        if session.is_empty() {
+            return Err("The session token is empty".to_string());
+        }
+
+        Ok(Self {
+            options: options.clone(),
+            session,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        })
+    }
+
+    /// `path` is relative to the base URL, e.g. `/2024/day/1/input`
+    pub fn get(&self, path: &str) -> Result<Response, String> {
+        self.request(path, &[])
+    }
+
+    /// Posts `form` url encoded
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<Response, String> {
+        self.request(path, form)
+    }
+
+    fn request(&self, path: &str, form: &[(&str, &str)]) -> Result<Response, String> {
+        self.throttle();
+
+        let url = format!("{}{}", self.options.base_url.trim_end_matches('/'), path);
+
+        let request = self.agent.request(if form.is_empty() { "GET" } else { "POST" }, &url).set("Cookie", &format!("session={}", self.session));
+        let result = if form.is_empty() { request.call() } else { request.send_form(form) };
+
+        // Error statuses still have a body worth showing
+        let response = match result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(format!("Request to {} failed: {}", url, e)),
+        };
+        let status = response.status();
+
+        Ok(Response {
+            status,
+            body: response.into_string().map_err(|e| format!("Invalid response from {}: {}", url, e))?,
+        })
+    }
+
+    /// Waits until the interval has passed since the last request
+    fn throttle(&self) {
+        let last = fs::read_to_string(LAST_REQUEST_FILE)
+            .ok()
+            .and_then(|e| e.trim().parse::<u64>().ok())
+            .unwrap_or(0);
+        let wait = (last + self.options.interval.as_millis() as u64).saturating_sub(unix_millis());
+
+        if wait > 0 {
+            let wait = Duration::from_millis(wait);
+            println!("{}", format!("Waiting {:?} before the next request", wait).bright_black());
+            thread::sleep(wait);
+        }
+
+        if let Some(parent) = Path::new(LAST_REQUEST_FILE).parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(LAST_REQUEST_FILE, unix_millis().to_string());
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|e| e.as_millis() as u64)
+        .unwrap_or(0)
+}
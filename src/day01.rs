@@ -1,5 +1,6 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use std::cmp::max;
 use std::cmp::min;
 use std::collections::HashMap;
@@ -7,10 +8,10 @@ use std::collections::HashMap;
 pub struct Part1;
 
 pub fn day01() -> Day<i32, i32> {
-    Day::new(1, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(1, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
-impl Part<i32> for Part1 {
+impl LinePart<i32> for Part1 {
     fn expect_test(&self) -> i32 {
         11
     }
@@ -30,7 +31,7 @@ impl Part<i32> for Part1 {
 
 pub struct Part2;
 
-impl Part<i32> for Part2 {
+impl LinePart<i32> for Part2 {
     fn expect_test(&self) -> i32 {
         31
     }
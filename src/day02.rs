@@ -1,13 +1,14 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 
 pub struct Part1;
 
 pub fn day02() -> Day<usize, usize> {
-    Day::new(2, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(2, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
-impl Part<usize> for Part1 {
+impl LinePart<usize> for Part1 {
     fn expect_test(&self) -> usize {
         2
     }
@@ -19,7 +20,7 @@ impl Part<usize> for Part1 {
 
 pub struct Part2;
 
-impl Part<usize> for Part2 {
+impl LinePart<usize> for Part2 {
     fn expect_test(&self) -> usize {
         4
     }
@@ -45,7 +46,7 @@ impl Part<usize> for Part2 {
                     }
                 }
 
-                return false;
+                false
             })
             .count()
     }
@@ -1,14 +1,15 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use regex::Regex;
 
 pub struct Part1;
 
 pub fn day03() -> Day<i32, i32> {
-    Day::new(3, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(3, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
-impl Part<i32> for Part1 {
+impl LinePart<i32> for Part1 {
     fn expect_test(&self) -> i32 {
         161
     }
@@ -20,7 +21,7 @@ impl Part<i32> for Part1 {
 
 pub struct Part2;
 
-impl Part<i32> for Part2 {
+impl LinePart<i32> for Part2 {
     fn expect_test(&self) -> i32 {
         48
     }
@@ -50,7 +51,7 @@ fn solve(input: &[String], all: bool) -> i32 {
                 return group_as_i32(2) * group_as_i32(3);
             }
 
-            return 0;
+            0
         })
         .sum()
 }
@@ -1,5 +1,6 @@
 use crate::harness::Day;
 use crate::harness::Part;
+use crate::input::{Grid, PuzzleInput};
 use std::ops::{Add, Mul, Neg};
 pub struct Part1;
 
@@ -8,30 +9,23 @@ pub fn day04() -> Day<usize, usize> {
 }
 
 impl Part<usize> for Part1 {
-    fn expect_test(&self) -> usize {
-        18
+    fn expect_test(&self) -> Option<usize> {
+        Some(18)
     }
 
-    fn solve(&self, input: &[String]) -> usize {
-        let input = Input::from(input);
+    fn solve_input(&self, input: &PuzzleInput, _: &()) -> usize {
+        let grid = input.grid();
 
         let mut count = 0;
 
-        for y in 0..input.height {
-            for x in 0..input.width {
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
                 let p = v(x as i32, y as i32);
 
                 for d in &Vec2::DIRECTIONS {
-                    let word = [
-                        input.get(&p),
-                        input.get(&(p + *d * 1)),
-                        input.get(&(p + *d * 2)),
-                        input.get(&(p + *d * 3)),
-                    ]
-                    .map(|e| e.map(|c| c.to_string()).unwrap_or("".to_string()))
-                    .join("");
-
-                    if word == "XMAS" {
+                    let word = [p, p + *d * 1, p + *d * 2, p + *d * 3].map(|e| get(&grid, &e));
+
+                    if &word == b"XMAS" {
                         count += 1;
                     }
                 }
@@ -45,30 +39,29 @@ impl Part<usize> for Part1 {
 pub struct Part2;
 
 impl Part<usize> for Part2 {
-    fn expect_test(&self) -> usize {
-        9
+    fn expect_test(&self) -> Option<usize> {
+        Some(9)
     }
 
-    fn solve(&self, input: &[String]) -> usize {
-        let input = Input::from(input);
+    fn solve_input(&self, input: &PuzzleInput, _: &()) -> usize {
+        let grid = input.grid();
 
         let mut count = 0;
 
-        for y in 0..input.height {
-            for x in 0..input.width {
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
                 let p = v(x as i32, y as i32);
 
                 let word = [
-                    input.get(&(p + Vec2::NORTH_WEST)),
-                    input.get(&(p + Vec2::NORTH_EAST)),
-                    input.get(&p),
-                    input.get(&(p + Vec2::SOUTH_WEST)),
-                    input.get(&(p + Vec2::SOUTH_EAST)),
+                    p + Vec2::NORTH_WEST,
+                    p + Vec2::NORTH_EAST,
+                    p,
+                    p + Vec2::SOUTH_WEST,
+                    p + Vec2::SOUTH_EAST,
                 ]
-                .map(|e| e.map(|c| c.to_string()).unwrap_or("".to_string()))
-                .join("");
+                .map(|e| get(&grid, &e));
 
-                if word == "MSAMS" || word == "MMASS" || word == "SMASM" || word == "SSAMM" {
+                if [b"MSAMS", b"MMASS", b"SMASM", b"SSAMM"].contains(&&word) {
                     count += 1;
                 }
             }
@@ -78,29 +71,9 @@ impl Part<usize> for Part2 {
     }
 }
 
-struct Input<'a> {
-    raw: &'a [String],
-    width: usize,
-    height: usize,
-}
-
-impl<'a> From<&'a [String]> for Input<'a> {
-    fn from(value: &'a [String]) -> Self {
-        Input {
-            width: value[0].len(),
-            height: value.len(),
-            raw: value,
-        }
-    }
-}
-
-impl<'a> Input<'a> {
-    fn get(&self, p: &Vec2) -> Option<char> {
-        self.raw
-            .get(p.y as usize)
-            .map(|s| s.chars().nth(p.x as usize))
-            .flatten()
-    }
+/// Outside of the grid counts as a letter that's never part of a word
+fn get(grid: &Grid, p: &Vec2) -> u8 {
+    grid.get(p.x, p.y).unwrap_or(b'.')
 }
 
 const fn v(x: i32, y: i32) -> Vec2 {
@@ -1,14 +1,15 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use std::collections::HashMap;
 
 pub struct Part1;
 
 pub fn day05() -> Day<u32, u32> {
-    Day::new(5, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(5, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
-impl Part<u32> for Part1 {
+impl LinePart<u32> for Part1 {
     fn expect_test(&self) -> u32 {
         143
     }
@@ -24,7 +25,7 @@ impl Part<u32> for Part1 {
 
 pub struct Part2;
 
-impl Part<u32> for Part2 {
+impl LinePart<u32> for Part2 {
     fn expect_test(&self) -> u32 {
         123
     }
@@ -1,15 +1,16 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use std::collections::HashSet;
 use std::ops::{Add, Mul, Neg};
 
 pub struct Part1;
 
 pub fn day06() -> Day<i32, i32> {
-    Day::new(6, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(6, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
-impl Part<i32> for Part1 {
+impl LinePart<i32> for Part1 {
     fn expect_test(&self) -> i32 {
         41
     }
@@ -30,7 +31,7 @@ impl Part<i32> for Part1 {
 
 pub struct Part2;
 
-impl Part<i32> for Part2 {
+impl LinePart<i32> for Part2 {
     fn expect_test(&self) -> i32 {
         6
     }
@@ -1,13 +1,14 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 
 pub struct Part1;
 
 pub fn day07() -> Day<u64, u64> {
-    Day::new(7, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(7, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
-impl Part<u64> for Part1 {
+impl LinePart<u64> for Part1 {
     fn expect_test(&self) -> u64 {
         3749
     }
@@ -21,7 +22,7 @@ impl Part<u64> for Part1 {
 
 pub struct Part2;
 
-impl Part<u64> for Part2 {
+impl LinePart<u64> for Part2 {
     fn expect_test(&self) -> u64 {
         11387
     }
@@ -1,15 +1,16 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use std::collections::HashMap;
 use std::mem::swap;
 
 pub struct Part1;
 
 pub fn day11() -> Day<usize, usize> {
-    Day::new(11, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(11, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
-impl Part<usize> for Part1 {
+impl LinePart<usize> for Part1 {
     fn expect_test(&self) -> usize {
         55312
     }
@@ -21,7 +22,7 @@ impl Part<usize> for Part1 {
 
 pub struct Part2;
 
-impl Part<usize> for Part2 {
+impl LinePart<usize> for Part2 {
     fn expect_test(&self) -> usize {
         65601038650482
     }
@@ -1,19 +1,25 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use std::collections::HashSet;
 use std::ops::{Add, Mul, Neg, Sub};
 
 pub fn day12() -> Day<u32, u32> {
-    Day::new(12, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(12, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
 pub struct Part1;
 
-impl Part<u32> for Part1 {
+impl LinePart<u32> for Part1 {
     fn expect_test(&self) -> u32 {
         1930
     }
 
+    /// The two smaller examples of the puzzle, the one with only A to E and the one with the O region around the X regions
+    fn additional_tests(&self) -> Vec<(&'static str, u32)> {
+        vec![("12_test_small.txt", 140), ("12_test_enclosed.txt", 772)]
+    }
+
     fn solve(&self, input: &[String]) -> u32 {
         let input = Input::from(input);
 
@@ -40,11 +46,21 @@ impl Part<u32> for Part1 {
 
 pub struct Part2;
 
-impl Part<u32> for Part2 {
+impl LinePart<u32> for Part2 {
     fn expect_test(&self) -> u32 {
         1206
     }
 
+    /// The examples of part 1 and the two of part 2, the E-shaped region and the two B regions touching diagonally
+    fn additional_tests(&self) -> Vec<(&'static str, u32)> {
+        vec![
+            ("12_test_small.txt", 80),
+            ("12_test_enclosed.txt", 436),
+            ("12_test_e.txt", 236),
+            ("12_test_diagonal.txt", 368),
+        ]
+    }
+
     fn solve(&self, input: &[String]) -> u32 {
         let input = Input::from(input);
 
@@ -233,11 +249,11 @@ impl Mul<i32> for Vec2 {
 
 impl Vec2 {
     fn perpendicular_directions(&self) -> Vec<Vec2> {
-        match self {
-            &Vec2::NORTH => vec![Vec2::EAST, Vec2::WEST],
-            &Vec2::SOUTH => vec![Vec2::EAST, Vec2::WEST],
-            &Vec2::EAST => vec![Vec2::NORTH, Vec2::SOUTH],
-            &Vec2::WEST => vec![Vec2::NORTH, Vec2::SOUTH],
+        match *self {
+            Vec2::NORTH => vec![Vec2::EAST, Vec2::WEST],
+            Vec2::SOUTH => vec![Vec2::EAST, Vec2::WEST],
+            Vec2::EAST => vec![Vec2::NORTH, Vec2::SOUTH],
+            Vec2::WEST => vec![Vec2::NORTH, Vec2::SOUTH],
             _ => panic!(),
         }
     }
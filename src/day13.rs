@@ -1,37 +1,41 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
+use crate::phase::phase;
 use regex::Regex;
 use std::sync::OnceLock;
 
 pub fn day13() -> Day<u64, u64> {
-    Day::new(13, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(13, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
 pub struct Part1;
 
-impl Part<u64> for Part1 {
+impl LinePart<u64> for Part1 {
     fn expect_test(&self) -> u64 {
         480
     }
 
     fn solve(&self, input: &[String]) -> u64 {
-        Input::from(input)
-            .machines
-            .iter()
-            .filter_map(|e| e.solve())
-            .sum()
+        phase("parse");
+        let input = Input::from(input);
+        phase("solve");
+
+        input.machines.iter().filter_map(|e| e.solve()).sum()
     }
 }
 
 pub struct Part2;
 
-impl Part<u64> for Part2 {
+impl LinePart<u64> for Part2 {
     fn expect_test(&self) -> u64 {
         875318608908
     }
 
     fn solve(&self, input: &[String]) -> u64 {
+        phase("parse");
         let mut input = Input::from(input);
+        phase("solve");
 
         for x in &mut input.machines {
             x.prize.x += 10000000000000.0;
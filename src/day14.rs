@@ -1,21 +1,43 @@
 use crate::harness::Day;
 use crate::harness::Part;
+use crate::input::PuzzleInput;
 use std::collections::HashSet;
 use std::ops::{Add, AddAssign, Mul, Neg, Sub};
 
-pub fn day14() -> Day<i32, i32> {
-    Day::new(14, Box::new(Part1 {}), Box::new(Part2 {}))
+pub fn day14() -> Day<i32, i32, Params> {
+    Day::with_params(
+        14,
+        Box::new(Part1 {}),
+        Box::new(Part2 {}),
+        Params {
+            width: 11,
+            height: 7,
+            has_tree: false,
+        },
+        Params {
+            width: 101,
+            height: 103,
+            has_tree: true,
+        },
+    )
+}
+
+pub struct Params {
+    width: i32,
+    height: i32,
+    /// The example never shows a tree, part 2 gives 0 for it instead of failing once every position was tried
+    has_tree: bool,
 }
 
 pub struct Part1;
 
-impl Part<i32> for Part1 {
-    fn expect_test(&self) -> i32 {
-        12
+impl Part<i32, Params> for Part1 {
+    fn expect_test(&self) -> Option<i32> {
+        Some(12)
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        let mut input = Input::from(input);
+    fn solve_input(&self, input: &PuzzleInput, params: &Params) -> i32 {
+        let mut input = Input::new(input, params);
 
         for robot in &mut input.robots {
             robot.walk_n(input.width, input.height, 100);
@@ -46,19 +68,16 @@ impl Part<i32> for Part1 {
 
 pub struct Part2;
 
-impl Part<i32> for Part2 {
-    fn expect_test(&self) -> i32 {
-        0
+impl Part<i32, Params> for Part2 {
+    fn expect_test(&self) -> Option<i32> {
+        Some(0)
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        let mut input = Input::from(input);
+    fn solve_input(&self, input: &PuzzleInput, params: &Params) -> i32 {
+        let mut input = Input::new(input, params);
 
-        if input.width < 50 {
-            return 0;
-        }
-
-        'outer: for iteration in 1.. {
+        // Positions repeat after width * height iterations, so there's no tree if we haven't found one by then
+        'outer: for iteration in 1..=input.width * input.height {
             for robot in &mut input.robots {
                 robot.walk_once(input.width, input.height);
             }
@@ -102,7 +121,9 @@ impl Part<i32> for Part2 {
             }
         }
 
-        panic!()
+        assert!(!params.has_tree, "No tree within {} iterations", input.width * input.height);
+
+        0
     }
 }
 
@@ -112,21 +133,6 @@ struct Robot {
     velocity: Vec2,
 }
 
-impl From<&str> for Robot {
-    fn from(value: &str) -> Self {
-        let mut nums = value
-            .split(" ")
-            .flat_map(|e| e.split("=").skip(1))
-            .flat_map(|e| e.split(","))
-            .map(|e| e.parse::<i32>().unwrap());
-
-        let position = v(nums.next().unwrap(), nums.next().unwrap());
-        let velocity = v(nums.next().unwrap(), nums.next().unwrap());
-
-        Robot { position, velocity }
-    }
-}
-
 impl Robot {
     fn walk_once(&mut self, width: i32, height: i32) {
         self.position += self.velocity;
@@ -148,21 +154,21 @@ struct Input {
     height: i32,
 }
 
-impl From<&[String]> for Input {
-    fn from(value: &[String]) -> Self {
-        let robots = value
-            .iter()
-            .filter(|e| !e.is_empty())
-            .map(|e| Robot::from(e.as_str()))
+impl Input {
+    fn new(value: &PuzzleInput, params: &Params) -> Self {
+        let numbers = value.ints().map(|e| e as i32).collect::<Vec<_>>();
+        let robots = numbers
+            .chunks_exact(4)
+            .map(|e| Robot {
+                position: v(e[0], e[1]),
+                velocity: v(e[2], e[3]),
+            })
             .collect::<Vec<_>>();
 
-        let width = robots.iter().map(|e| e.position.x).max().unwrap() + 1;
-        let height = robots.iter().map(|e| e.position.y).max().unwrap() + 1;
-
         Input {
             robots,
-            width,
-            height,
+            width: params.width,
+            height: params.height,
         }
     }
 }
@@ -1,14 +1,15 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use std::ops::{Add, AddAssign, Mul, Neg, Sub};
 
 pub fn day15() -> Day<u32, u32> {
-    Day::new(15, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(15, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
 pub struct Part1;
 
-impl Part<u32> for Part1 {
+impl LinePart<u32> for Part1 {
     fn expect_test(&self) -> u32 {
         10092
     }
@@ -32,7 +33,7 @@ impl Part<u32> for Part1 {
 
 pub struct Part2;
 
-impl Part<u32> for Part2 {
+impl LinePart<u32> for Part2 {
     fn expect_test(&self) -> u32 {
         9021
     }
@@ -230,7 +231,7 @@ impl Input {
         self.map = self
             .map
             .iter()
-            .map(|e| e.into_iter().flat_map(|&e| vec![e, None]).collect())
+            .map(|e| e.iter().flat_map(|&e| vec![e, None]).collect())
             .collect();
     }
 }
@@ -1,45 +1,43 @@
 use crate::harness::Day;
 use crate::harness::Part;
+use crate::input::PuzzleInput;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::ops::{Add, AddAssign, Mul, Neg, Sub};
 
 pub fn day16() -> Day<i32, i32> {
-    Day::new(16, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(16, Box::new(Part1 {}), Box::new(Part2 {})).parse::<Search>()
 }
 
 pub struct Part1;
 
 impl Part<i32> for Part1 {
-    fn expect_test(&self) -> i32 {
-        7036
+    fn expect_test(&self) -> Option<i32> {
+        Some(7036)
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        let input = Input::from(input);
-
-        best_cost(&input).unwrap().0
+    fn solve_input(&self, input: &PuzzleInput, _: &()) -> i32 {
+        input.parsed::<Search>().cost
     }
 }
 
 pub struct Part2;
 
 impl Part<i32> for Part2 {
-    fn expect_test(&self) -> i32 {
-        45
+    fn expect_test(&self) -> Option<i32> {
+        Some(45)
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        let input = Input::from(input);
-
-        let (_, end_node, came_from) = best_cost(&input).unwrap();
+    fn solve_input(&self, input: &PuzzleInput, _: &()) -> i32 {
+        // Walks back along the paths of the search the day runs before either part
+        let search = input.parsed::<Search>();
 
-        let mut open = vec![end_node];
+        let mut open = vec![search.end_node];
         let mut closed = HashSet::new();
 
         while let Some(current) = open.pop() {
             if closed.insert(current) {
-                if let Some(neighbours) = came_from.get(&current) {
+                if let Some(neighbours) = search.came_from.get(&current) {
                     for x in neighbours {
                         open.push(*x);
                     }
@@ -91,13 +89,13 @@ impl Input {
     }
 }
 
-impl From<&[String]> for Input {
-    fn from(value: &[String]) -> Self {
+impl From<&PuzzleInput> for Input {
+    fn from(value: &PuzzleInput) -> Self {
         let mut start_position = None;
         let mut end_position = None;
 
         let map = value
-            .iter()
+            .lines()
             .filter(|e| !e.is_empty())
             .enumerate()
             .map(|(y, s)| {
@@ -144,7 +142,20 @@ impl Ord for OpenNode {
     }
 }
 
-fn best_cost(input: &Input) -> Option<(i32, Node, HashMap<Node, Vec<Node>>)> {
+/// The cheapest path to the end and every node's predecessors on cheapest paths
+struct Search {
+    cost: i32,
+    end_node: Node,
+    came_from: HashMap<Node, Vec<Node>>,
+}
+
+impl From<&PuzzleInput> for Search {
+    fn from(value: &PuzzleInput) -> Self {
+        best_cost(&Input::from(value)).unwrap()
+    }
+}
+
+fn best_cost(input: &Input) -> Option<Search> {
     let start = Node::new(input.start_position, input.start_direction);
     let mut open_heap = BinaryHeap::from([OpenNode(start, 0)]);
     let mut closed_set = HashSet::new();
@@ -161,7 +172,11 @@ fn best_cost(input: &Input) -> Option<(i32, Node, HashMap<Node, Vec<Node>>)> {
             return g_scores
                 .iter()
                 .find(|(&node, _)| node.position == input.end_position)
-                .map(|(&node, &score)| (score, node, came_from));
+                .map(|(&node, &score)| Search {
+                    cost: score,
+                    end_node: node,
+                    came_from,
+                });
         }
 
         if !closed_set.insert(current) {
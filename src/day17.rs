@@ -1,14 +1,15 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use std::ops::BitXor;
 
 pub fn day17() -> Day<String, u64> {
-    Day::new(17, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(17, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
 pub struct Part1;
 
-impl Part<String> for Part1 {
+impl LinePart<String> for Part1 {
     fn expect_test(&self) -> String {
         "4,6,3,5,6,3,5,2,1,0".to_string()
     }
@@ -29,7 +30,7 @@ impl Part<String> for Part1 {
 
 pub struct Part2;
 
-impl Part<u64> for Part2 {
+impl LinePart<u64> for Part2 {
     fn expect_test(&self) -> u64 {
         117440
     }
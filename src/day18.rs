@@ -1,42 +1,53 @@
 use crate::harness::Day;
 use crate::harness::Part;
+use crate::input::PuzzleInput;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
 use std::ops::{Add, AddAssign, Mul, Neg, Sub};
 
-pub fn day18() -> Day<i32, String> {
-    Day::new(18, Box::new(Part1 {}), Box::new(Part2 {}))
+pub fn day18() -> Day<i32, String, Params> {
+    Day::with_params(
+        18,
+        Box::new(Part1 {}),
+        Box::new(Part2 {}),
+        Params { size: 7, bytes: 12 },
+        Params {
+            size: 71,
+            bytes: 1024,
+        },
+    )
+}
+
+pub struct Params {
+    size: i32,
+    bytes: usize,
 }
 
 pub struct Part1;
 
-impl Part<i32> for Part1 {
-    fn expect_test(&self) -> i32 {
-        22
+impl Part<i32, Params> for Part1 {
+    fn expect_test(&self) -> Option<i32> {
+        Some(22)
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        let input = Input::from(input);
+    fn solve_input(&self, input: &PuzzleInput, params: &Params) -> i32 {
+        let input = Input::new(input.string_lines(), params);
 
-        let start = if input.width < 10 { 12 } else { 1024 };
-
-        search(&input, input.build_grid(start)).unwrap()
+        search(&input, input.build_grid(params.bytes)).unwrap()
     }
 }
 
 pub struct Part2;
 
-impl Part<String> for Part2 {
-    fn expect_test(&self) -> String {
-        "6,1".to_string()
+impl Part<String, Params> for Part2 {
+    fn expect_test(&self) -> Option<String> {
+        Some("6,1".to_string())
     }
 
-    fn solve(&self, input: &[String]) -> String {
-        let input = Input::from(input);
-
-        let start = if input.width < 10 { 12 } else { 1024 };
+    fn solve_input(&self, input: &PuzzleInput, params: &Params) -> String {
+        let input = Input::new(input.string_lines(), params);
 
-        let mut min = start + 1;
+        let mut min = params.bytes + 1;
         let mut max = input.blockers.len() - 1;
 
         loop {
@@ -75,8 +86,8 @@ struct Input {
     end: Vec2,
 }
 
-impl From<&[String]> for Input {
-    fn from(value: &[String]) -> Self {
+impl Input {
+    fn new(value: &[String], params: &Params) -> Self {
         let blockers = value
             .iter()
             .filter(|e| !e.is_empty())
@@ -86,22 +97,15 @@ impl From<&[String]> for Input {
             })
             .collect::<Vec<_>>();
 
-        let end = v(
-            blockers.iter().map(|e| e.x).max().unwrap(),
-            blockers.iter().map(|e| e.y).max().unwrap(),
-        );
-
         Self {
             blockers,
-            width: end.x as usize + 1,
-            height: end.y as usize + 1,
+            width: params.size as usize,
+            height: params.size as usize,
             start: v(0, 0),
-            end,
+            end: v(params.size - 1, params.size - 1),
         }
     }
-}
 
-impl Input {
     fn build_grid(&self, max: usize) -> Vec<Vec<Tile>> {
         let mut result = vec![vec![Tile::Empty; self.width]; self.height];
 
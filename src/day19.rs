@@ -1,14 +1,15 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use std::collections::HashMap;
 
 pub fn day19() -> Day<u64, u64> {
-    Day::new(19, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(19, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
 pub struct Part1;
 
-impl Part<u64> for Part1 {
+impl LinePart<u64> for Part1 {
     fn expect_test(&self) -> u64 {
         6
     }
@@ -30,7 +31,7 @@ impl Part<u64> for Part1 {
 
 pub struct Part2;
 
-impl Part<u64> for Part2 {
+impl LinePart<u64> for Part2 {
     fn expect_test(&self) -> u64 {
         16
     }
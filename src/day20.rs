@@ -1,20 +1,32 @@
 use crate::harness::Day;
 use crate::harness::Part;
+use crate::input::PuzzleInput;
 use std::ops::{Add, AddAssign, Mul, Neg, Sub};
 
-pub fn day20() -> Day<i32, i32> {
-    Day::new(20, Box::new(Part1 {}), Box::new(Part2 {}))
+pub fn day20() -> Day<i32, i32, Params> {
+    Day::with_params(
+        20,
+        Box::new(Part1 {}),
+        Box::new(Part2 {}),
+        Params { threshold: 50 },
+        Params { threshold: 100 },
+    )
+}
+
+/// Minimum number of picoseconds a cheat has to save to be counted
+pub struct Params {
+    threshold: i32,
 }
 
 pub struct Part1;
 
-impl Part<i32> for Part1 {
-    fn expect_test(&self) -> i32 {
-        0
+impl Part<i32, Params> for Part1 {
+    fn expect_test(&self) -> Option<i32> {
+        Some(1)
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        let input = Input::from(input);
+    fn solve_input(&self, input: &PuzzleInput, params: &Params) -> i32 {
+        let input = Input::from(input.string_lines());
 
         let dist = input.dijkstra();
 
@@ -38,7 +50,7 @@ impl Part<i32> for Part1 {
                         if let Some(other_dist) = get(other) {
                             let diff = (current_dist.abs_diff(other_dist) as i32) - 2;
 
-                            if diff >= 100 {
+                            if diff >= params.threshold {
                                 result += 1;
                             }
                         }
@@ -53,13 +65,13 @@ impl Part<i32> for Part1 {
 
 pub struct Part2;
 
-impl Part<i32> for Part2 {
-    fn expect_test(&self) -> i32 {
-        0
+impl Part<i32, Params> for Part2 {
+    fn expect_test(&self) -> Option<i32> {
+        Some(285)
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        let input = Input::from(input);
+    fn solve_input(&self, input: &PuzzleInput, params: &Params) -> i32 {
+        let input = Input::from(input.string_lines());
 
         let dist = input.dijkstra();
 
@@ -86,7 +98,7 @@ impl Part<i32> for Part2 {
                                 let dist = y_diff + x_diff.abs();
                                 let diff = current_dist.abs_diff(other_dist) as i32 - dist;
 
-                                if diff >= 100 {
+                                if diff >= params.threshold {
                                     result += 1;
                                 }
                             }
@@ -1,17 +1,18 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use regex::Regex;
 use std::collections::HashMap;
 use std::iter;
 use std::ops::{Add, AddAssign, Mul, Neg, Sub};
 
 pub fn day21() -> Day<u64, u64> {
-    Day::new(21, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(21, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
 pub struct Part1;
 
-impl Part<u64> for Part1 {
+impl LinePart<u64> for Part1 {
     fn expect_test(&self) -> u64 {
         126384
     }
@@ -23,17 +24,13 @@ impl Part<u64> for Part1 {
 
 pub struct Part2;
 
-impl Part<u64> for Part2 {
+impl LinePart<u64> for Part2 {
     fn expect_test(&self) -> u64 {
-        0
+        154115708116294
     }
 
     fn solve(&self, input: &[String]) -> u64 {
-        if input[0].contains("029") {
-            0
-        } else {
-            solve(input, 25)
-        }
+        solve(input, 25)
     }
 }
 
@@ -75,7 +72,7 @@ impl Keypad {
         index: usize,
         running_result: &mut Vec<&'a str>,
         fragment_cache: &mut HashMap<&'a str, Vec<Vec<&'a str>>>,
-    ) -> Vec<Vec<&str>> {
+    ) -> Vec<Vec<&'a str>> {
         if index == 0 {
             if let Some(result) = fragment_cache.get(sequence) {
                 return result.clone();
@@ -119,13 +116,13 @@ impl Keypad {
         let mut full_cache = HashMap::new();
 
         s.iter()
-            .fold(HashMap::new(), |mut acc, &e| {
+            .fold(HashMap::<_, u64>::new(), |mut acc, &e| {
                 *acc.entry(e).or_default() += 1;
                 acc
             })
             .into_iter()
             .map(|(s, count)| {
-                self.solve_full_sequence_rec(n, s, count, &mut full_cache, fragment_cache)
+                count * self.solve_full_sequence_rec(n, s, &mut full_cache, fragment_cache)
             })
             .sum::<u64>()
     }
@@ -134,12 +131,11 @@ impl Keypad {
         &'a self,
         depth: usize,
         fragment: &'a str,
-        count: u64,
         full_cache: &mut HashMap<(&'a str, usize), u64>,
         fragment_cache: &mut HashMap<&'a str, Vec<Vec<&'a str>>>,
     ) -> u64 {
         if depth == 0 {
-            return fragment.len() as u64 * count;
+            return fragment.len() as u64;
         }
 
         let key = (fragment, depth);
@@ -156,13 +152,7 @@ impl Keypad {
             .map(|x| {
                 x.iter()
                     .map(|s| {
-                        self.solve_full_sequence_rec(
-                            depth - 1,
-                            s,
-                            count,
-                            full_cache,
-                            fragment_cache,
-                        )
+                        self.solve_full_sequence_rec(depth - 1, s, full_cache, fragment_cache)
                     })
                     .sum::<u64>()
             })
@@ -1,14 +1,15 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use std::ops::BitXor;
 
 pub fn day22() -> Day<u64, u32> {
-    Day::new(22, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(22, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
 pub struct Part1;
 
-impl Part<u64> for Part1 {
+impl LinePart<u64> for Part1 {
     fn expect_test(&self) -> u64 {
         37327623
     }
@@ -22,7 +23,7 @@ impl Part<u64> for Part1 {
 
 pub struct Part2;
 
-impl Part<u32> for Part2 {
+impl LinePart<u32> for Part2 {
     fn expect_test(&self) -> u32 {
         23
     }
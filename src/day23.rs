@@ -1,14 +1,15 @@
 use crate::harness::Day;
-use crate::harness::Part;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use std::collections::{HashMap, HashSet};
 
 pub fn day23() -> Day<u32, String> {
-    Day::new(23, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(23, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
 }
 
 pub struct Part1;
 
-impl Part<u32> for Part1 {
+impl LinePart<u32> for Part1 {
     fn expect_test(&self) -> u32 {
         7
     }
@@ -29,7 +30,7 @@ impl Part<u32> for Part1 {
 
 pub struct Part2;
 
-impl Part<String> for Part2 {
+impl LinePart<String> for Part2 {
     fn expect_test(&self) -> String {
         "co,de,ka,ta".to_string()
     }
@@ -1,22 +1,31 @@
 use crate::harness::Day;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use crate::harness::Part;
+use crate::input::PuzzleInput;
+use crate::phase::phase;
 use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::{BitAnd, BitOr, BitXor};
 
 pub fn day24() -> Day<u64, String> {
-    Day::new(24, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(24, Box::new(Lines(Part1 {})), Box::new(Part2 {}))
 }
 
+/// Number of gate output pairs that were swapped in the adder circuit
+const SWAPPED_PAIRS: usize = 4;
+
 pub struct Part1;
 
-impl Part<u64> for Part1 {
+impl LinePart<u64> for Part1 {
     fn expect_test(&self) -> u64 {
         2024
     }
 
     fn solve(&self, input: &[String]) -> u64 {
+        phase("parse");
         let input = Input::from(input);
+        phase("solve");
 
         let mut values = input.initial.clone();
 
@@ -42,9 +51,9 @@ impl Part<u64> for Part1 {
                     if let Some(operand1) = values.get(operand1) {
                         if let Some(operand2) = values.get(operand2) {
                             let result_value = match operator {
-                                Operator::AND => operand1.bitand(operand2),
-                                Operator::OR => operand1.bitor(operand2),
-                                Operator::XOR => operand1.bitxor(operand2),
+                                Operator::And => operand1.bitand(operand2),
+                                Operator::Or => operand1.bitor(operand2),
+                                Operator::Xor => operand1.bitxor(operand2),
                             };
 
                             values.insert(result, result_value);
@@ -66,15 +75,15 @@ impl Part<u64> for Part1 {
 pub struct Part2;
 
 impl Part<String> for Part2 {
-    fn expect_test(&self) -> String {
-        "".to_string()
+    fn expect_test(&self) -> Option<String> {
+        // The examples aren't adder circuits, so there's nothing to repair
+        None
     }
 
-    fn solve(&self, input: &[String]) -> String {
-        if input.len() < 100 {
-            return "".to_string();
-        }
-        let input = Input::from(input);
+    fn solve_input(&self, input: &PuzzleInput, _: &()) -> String {
+        phase("parse");
+        let input = Input::from(input.string_lines());
+        phase("solve");
 
         let (mut broken_gates, adders) = solve(input.clone());
 
@@ -111,7 +120,7 @@ impl Part<String> for Part2 {
 
         let mut good_swaps = vec![];
 
-        for i in 0..broken_gates.len() - 1 {
+        for i in 0..broken_gates.len() {
             for j in i + 1..broken_gates.len() {
                 let mut current_input = input.clone();
 
@@ -127,31 +136,33 @@ impl Part<String> for Part2 {
             }
         }
 
+        // Not an adder circuit with swapped outputs after all, there's nothing to repair
+        if good_swaps.len() < SWAPPED_PAIRS {
+            return String::new();
+        }
+
         let mut best_count = usize::MAX;
-        let mut best_swaps = [0; 4];
+        let mut best_swaps = vec![];
+        let mut swaps = (0..SWAPPED_PAIRS).collect::<Vec<_>>();
 
-        for i in 0..good_swaps.len() - 3 {
-            for j in i + 1..good_swaps.len() - 2 {
-                for k in j + 1..good_swaps.len() - 1 {
-                    for l in k + 1..good_swaps.len() {
-                        let mut current_input = input.clone();
+        loop {
+            let mut current_input = input.clone();
 
-                        let swaps = [i, j, k, l];
+            for &x in &swaps {
+                let (id1, id2) = good_swaps[x];
 
-                        for &x in &swaps {
-                            let (id1, id2) = good_swaps[x];
+                current_input.gate_mut(id1).result = input.gate(id2).result;
+                current_input.gate_mut(id2).result = input.gate(id1).result;
+            }
 
-                            current_input.gate_mut(id1).result = input.gate(id2).result;
-                            current_input.gate_mut(id2).result = input.gate(id1).result;
-                        }
+            let i1 = solve(current_input).0.len();
+            if i1 < best_count {
+                best_count = i1;
+                best_swaps = swaps.clone();
+            }
 
-                        let i1 = solve(current_input).0.len();
-                        if i1 < best_count {
-                            best_count = i1;
-                            best_swaps = swaps;
-                        }
-                    }
-                }
+            if !next_combination(&mut swaps, good_swaps.len()) {
+                break;
             }
         }
 
@@ -165,6 +176,29 @@ impl Part<String> for Part2 {
     }
 }
 
+/// Advances `indices` to the next combination of `indices.len()` elements out of `0..n`
+fn next_combination(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+
+    if n < k {
+        return false;
+    }
+
+    for i in (0..k).rev() {
+        if indices[i] < n - k + i {
+            indices[i] += 1;
+
+            for j in i + 1..k {
+                indices[j] = indices[j - 1] + 1;
+            }
+
+            return true;
+        }
+    }
+
+    false
+}
+
 fn solve(mut input: Input) -> (Vec<Gate>, Vec<FullAdder>) {
     let mut adders = (0..46).map(|_| FullAdder::default()).collect::<Vec<_>>();
 
@@ -180,13 +214,13 @@ fn solve(mut input: Input) -> (Vec<Gate>, Vec<FullAdder>) {
                 broken_gates.push(input.gates.remove(i));
             } else {
                 match gate.operator {
-                    Operator::AND => {
+                    Operator::And => {
                         adders[id].half_adder1.and = Some(input.gates.remove(i));
                     }
-                    Operator::XOR => {
+                    Operator::Xor => {
                         adders[id].half_adder1.xor = Some(input.gates.remove(i));
                     }
-                    Operator::OR => {
+                    Operator::Or => {
                         broken_gates.push(input.gates.remove(i));
                     }
                 }
@@ -212,7 +246,7 @@ fn solve(mut input: Input) -> (Vec<Gate>, Vec<FullAdder>) {
             }
 
             match gate.operator {
-                Operator::XOR => {
+                Operator::Xor => {
                     adders[id].half_adder2.xor = Some(input.gates.remove(i));
                 }
                 _ => {
@@ -227,7 +261,7 @@ fn solve(mut input: Input) -> (Vec<Gate>, Vec<FullAdder>) {
     for x in &mut adders {
         if let Some(xor) = &x.half_adder1.xor {
             let position = input.gates.iter().position(|e| {
-                matches!(e.operator, Operator::AND)
+                matches!(e.operator, Operator::And)
                     && (xor.result == e.operand1 || xor.result == e.operand2)
             });
 
@@ -241,7 +275,7 @@ fn solve(mut input: Input) -> (Vec<Gate>, Vec<FullAdder>) {
     for x in &mut adders {
         if let Some(and) = &x.half_adder2.and {
             let position = input.gates.iter().position(|e| {
-                matches!(e.operator, Operator::OR)
+                matches!(e.operator, Operator::Or)
                     && (and.result == e.operand1 || and.result == e.operand2)
             });
 
@@ -300,9 +334,9 @@ impl<'a> Display for FullAdder<'a> {
 
 #[derive(Debug, Copy, Clone)]
 enum Operator {
-    AND,
-    OR,
-    XOR,
+    And,
+    Or,
+    Xor,
 }
 
 impl TryFrom<&str> for Operator {
@@ -310,9 +344,9 @@ impl TryFrom<&str> for Operator {
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
-            "AND" => Ok(Operator::AND),
-            "OR" => Ok(Operator::OR),
-            "XOR" => Ok(Operator::XOR),
+            "AND" => Ok(Operator::And),
+            "OR" => Ok(Operator::Or),
+            "XOR" => Ok(Operator::Xor),
             _ => Err(()),
         }
     }
@@ -1,19 +1,22 @@
 use crate::harness::Day;
+use crate::harness::LinePart;
+use crate::harness::Lines;
 use crate::harness::Part;
+use crate::input::{Grid, PuzzleInput};
 use std::ops::{BitAnd, BitOr, Shl};
 
 pub fn day25() -> Day<u32, ()> {
-    Day::new(25, Box::new(Part1 {}), Box::new(Part2 {}))
+    Day::new(25, Box::new(Part1 {}), Box::new(Lines(Part2 {})))
 }
 
 pub struct Part1;
 
 impl Part<u32> for Part1 {
-    fn expect_test(&self) -> u32 {
-        3
+    fn expect_test(&self) -> Option<u32> {
+        Some(3)
     }
 
-    fn solve(&self, input: &[String]) -> u32 {
+    fn solve_input(&self, input: &PuzzleInput, _: &()) -> u32 {
         let input = Input::from(input);
 
         let mut result = 0;
@@ -30,7 +33,7 @@ impl Part<u32> for Part1 {
 
 pub struct Part2;
 
-impl Part<()> for Part2 {
+impl LinePart<()> for Part2 {
     fn expect_test(&self) {}
 
     fn solve(&self, _input: &[String]) {}
@@ -39,13 +42,12 @@ impl Part<()> for Part2 {
 #[derive(Debug)]
 struct Schematic(u32);
 
-impl From<&[String]> for Schematic {
-    fn from(value: &[String]) -> Self {
-        let pattern = value[1..value.len() - 1]
-            .iter()
-            .flat_map(|s| s.chars())
+impl From<Grid<'_>> for Schematic {
+    fn from(value: Grid) -> Self {
+        let pattern = (1..value.height() - 1)
+            .flat_map(|y| value.row(y))
             .enumerate()
-            .map(|(i, c)| ((c == '#') as u32).shl(i))
+            .map(|(i, &c)| ((c == b'#') as u32).shl(i))
             .fold(0, |a, b| a.bitor(b));
 
         Self(pattern)
@@ -63,16 +65,16 @@ struct Input {
     keys: Vec<Schematic>,
 }
 
-impl From<&[String]> for Input {
-    fn from(value: &[String]) -> Self {
+impl From<&PuzzleInput> for Input {
+    fn from(value: &PuzzleInput) -> Self {
         let mut locks = vec![];
         let mut keys = vec![];
 
-        for e in value.split(|s| s.is_empty()).filter(|e| !e.is_empty()) {
-            if e[0].starts_with('#') {
-                locks.push(e.into());
+        for e in value.blocks() {
+            if e.starts_with('#') {
+                locks.push(Grid::new(e).into());
             } else {
-                keys.push(e.into());
+                keys.push(Grid::new(e).into());
             }
         }
 
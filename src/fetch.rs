@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::client::{Client, ClientOptions};
+use aoc_2024::harness::input_dir;
+
+/// Downloads the inputs of the days that aren't cached in the input directory of the year yet, returns the number of failures
+pub fn fetch_days(year: u16, ids: &[usize], options: &ClientOptions) -> usize {
+    let mut client = None;
+    let mut failures = 0;
+
+    for &id in ids {
+        let path = input_dir(year).join(format!("{:0>2}.txt", id));
+
+        if is_cached(&path) {
+            println!("Day{:0>2} {} {}", id, "cached".bright_black(), path.display());
+            continue;
+        }
+
+        // Only needs a session token once something is actually downloaded
+        if client.is_none() {
+            match Client::new(options) {
+                Ok(c) => client = Some(c),
+                Err(e) => {
+                    eprintln!("{}", e.red());
+                    return failures + ids.len();
+                }
+            }
+        }
+
+        match download(client.as_ref().unwrap(), year, id, &path) {
+            Ok(()) => println!("Day{:0>2} {} {}", id, "downloaded".green(), path.display()),
+            Err(e) => {
+                eprintln!("{}", format!("Day{:0>2} failed: {}", id, e).red());
+                failures += 1;
+            }
+        }
+    }
+
+    failures
+}
+
+fn is_cached(path: &Path) -> bool {
+    fs::metadata(path).map(|e| e.is_file() && e.len() > 0).unwrap_or(false)
+}
+
+fn download(client: &Client, year: u16, id: usize, path: &Path) -> Result<(), String> {
+    let response = client.get(&format!("/{}/day/{}/input", year, id))?;
+
+    if response.status != 200 {
+        return Err(format!("HTTP {} {}", response.status, response.body.lines().next().unwrap_or("").trim()));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("{}: {}", parent.display(), e))?;
+    }
+
+    // Written next to the target first, so an interrupted download never looks like a cached input
+    let partial = path.with_extension("txt.part");
+    fs::write(&partial, &response.body).map_err(|e| format!("{}: {}", partial.display(), e))?;
+    fs::rename(&partial, path).map_err(|e| format!("{}: {}", path.display(), e))
+}
@@ -1,142 +1,1014 @@
 #![allow(dead_code)]
 
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::fs;
-use std::path::Path;
+use std::io::{self, Error, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{mpsc, Arc, OnceLock};
+use std::thread;
 use std::time::{Duration, Instant};
 
 use colored::Colorize;
 
-pub trait AocResult: Debug + PartialEq + Sized {}
+use crate::bench::{BenchOptions, Stats};
+use crate::input::PuzzleInput;
+use crate::memory::{self, Usage};
+use crate::phase::{self, Phases};
+use crate::results;
 
-impl<T: Debug + PartialEq> AocResult for T {}
+/// Solvers can recurse deeply, so threads get as much stack as the main thread usually has
+const STACK_SIZE: usize = 8 * 1024 * 1024;
 
-pub trait Part<R: AocResult> {
+/// Set when the records are printed in a machine readable format instead
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Timed out parts whose threads are still running, see `watched`
+static RUNAWAY: AtomicUsize = AtomicUsize::new(0);
+
+const RUNNING: u8 = 0;
+const DONE: u8 = 1;
+const ABANDONED: u8 = 2;
+
+thread_local! {
+    static OUTPUT_BUFFER: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Like `println!`, but goes to the output buffer of the current thread while a day runs in parallel
+macro_rules! output {
+    ($($arg:tt)*) => {
+        write_line(format!($($arg)*))
+    };
+}
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+fn write_line(line: String) {
+    if QUIET.load(Ordering::Relaxed) {
+        return;
+    }
+
+    OUTPUT_BUFFER.with_borrow_mut(|buffer| match buffer {
+        Some(buffer) => {
+            buffer.push_str(&line);
+            buffer.push('\n');
+        }
+        None => println!("{}", line),
+    })
+}
+
+fn buffered<R, F: FnOnce() -> R>(f: F) -> (R, String) {
+    OUTPUT_BUFFER.set(Some(String::new()));
+    let result = f();
+    (result, OUTPUT_BUFFER.take().unwrap_or_default())
+}
+
+pub trait AocResult: Debug + PartialEq + Sized {
+    fn parse_answer(value: &str) -> Option<Self>;
+}
+
+macro_rules! from_str_result {
+    ($($t:ty),*) => {
+        $(
+            impl AocResult for $t {
+                fn parse_answer(value: &str) -> Option<Self> {
+                    value.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+from_str_result!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, String);
+
+impl AocResult for () {
+    fn parse_answer(value: &str) -> Option<Self> {
+        (value == "()").then_some(())
+    }
+}
+
+impl AocResult for &'static str {
+    fn parse_answer(value: &str) -> Option<Self> {
+        (value == NOT_IMPLEMENTED).then_some(NOT_IMPLEMENTED)
+    }
+}
+
+/// A puzzle part, optionally with parameters that differ between the test and actual inputs
+pub trait Part<R: AocResult, P = ()> {
+    /// None if the examples don't apply to this part, its test is reported as not applicable instead of running
+    fn expect_test(&self) -> Option<R>;
+
+    /// What the harness calls, parts that want one `String` per line implement `LinePart` instead
+    fn solve_input(&self, input: &PuzzleInput, params: &P) -> R;
+
+    /// Further examples as (file name in the input directory, expected result), run after the main test.
+    /// Their files are named like the test input with a suffix, e.g. `12_test_small.txt`.
+    fn additional_tests(&self) -> Vec<(&'static str, R)> {
+        vec![]
+    }
+}
+
+/// A part that gets the input as one `String` per line and no parameters, `Lines` makes it a `Part`
+pub trait LinePart<R: AocResult> {
     fn expect_test(&self) -> R;
+
     fn solve(&self, input: &[String]) -> R;
+
+    /// Further examples as (file name in the input directory, expected result), run after the main test.
+    /// Their files are named like the test input with a suffix, e.g. `12_test_small.txt`.
+    fn additional_tests(&self) -> Vec<(&'static str, R)> {
+        vec![]
+    }
+}
+
+/// Adapts a `LinePart` to a `Part` of a day with any parameters
+pub struct Lines<T>(pub T);
+
+impl<R: AocResult, P, T: LinePart<R>> Part<R, P> for Lines<T> {
+    fn expect_test(&self) -> Option<R> {
+        Some(self.0.expect_test())
+    }
+
+    fn solve_input(&self, input: &PuzzleInput, _: &P) -> R {
+        self.0.solve(input.string_lines())
+    }
+
+    fn additional_tests(&self) -> Vec<(&'static str, R)> {
+        self.0.additional_tests()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub part: Option<u8>,
+    pub test: bool,
+    pub actual: bool,
+    pub bench: Option<BenchOptions>,
+    pub timeout: Option<Duration>,
+    /// Replaces the actual input, the tests and the answers file are skipped then
+    pub input: Option<CustomInput>,
+    /// Runs the replaced input with the parameters of the test inputs, e.g. for an example in another file
+    pub test_params: bool,
+    /// Replaces the known answer of the actual input
+    pub expect: Option<String>,
 }
 
-trait Run<R: AocResult> {
-    fn run_test(&self, id: u8, input: &[String]) -> Duration;
-    fn run_actual(&self, id: u8, input: &[String]) -> Duration;
-    fn run_all(&self, id: u8, test_input: &[String], actual_input: &[String]) -> Duration;
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            part: None,
+            test: true,
+            actual: true,
+            bench: None,
+            timeout: None,
+            input: None,
+            test_params: false,
+            expect: None,
+        }
+    }
 }
 
-impl<T: Part<R> + ?Sized, R: AocResult> Run<R> for T {
-    fn run_test(&self, id: u8, input: &[String]) -> Duration {
-        let (actual, duration) = timed(|| { self.solve(input) });
-        let expected = self.expect_test();
-        assert_eq!(actual, expected, "Part {} test failed after {:?}: Expected {:?} but got {:?}", id, duration, expected, actual);
-        println!("{}", format!("Part {} test was {} {:>10}", id, "      successful".green(), format!("{:?}", duration).purple()).bright_yellow());
-        duration
+impl RunOptions {
+    fn runs_part(&self, id: u8) -> bool {
+        self.part.is_none_or(|e| e == id)
     }
+}
+
+/// An input from anywhere instead of the input directory
+#[derive(Debug, Clone)]
+pub struct CustomInput {
+    name: String,
+    content: Arc<PuzzleInput>,
+}
+
+impl CustomInput {
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let name = path.to_string_lossy().into_owned();
+        let content = read_input(&name).map_err(|e| format!("{}: {}", name, e))?;
 
-    fn run_actual(&self, id: u8, input: &[String]) -> Duration {
-        let (actual, duration) = timed(|| { self.solve(input) });
-        println!("{}", format!("Part {} output {:>18} {:>10}", id, format!("{:?}", actual).blue(), format!("{:?}", duration).purple()));
-        duration
+        Ok(Self { name, content: Arc::new(content) })
     }
 
-    fn run_all(&self, id: u8, test_input: &[String], actual_input: &[String]) -> Duration {
-        self.run_test(id, test_input);
-        self.run_actual(id, actual_input)
+    pub fn from_stdin() -> Result<Self, String> {
+        let content = io::read_to_string(io::stdin()).map_err(|e| format!("stdin: {}", e))?;
+
+        Ok(Self { name: "stdin".to_string(), content: Arc::new(loaded(content)) })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Test,
+    Actual,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Status {
+    Passed,
+    Failed,
+    Unknown,
+    Skipped,
+    Panicked,
+    TimedOut,
+    NotImplemented,
+    /// The examples don't apply to the part
+    NotApplicable,
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub kind: Kind,
+    pub input: String,
+    pub answer: String,
+    pub expected: Option<String>,
+    pub status: Status,
+    pub duration: Duration,
+    /// Time spent in each phase the part marked with `phase::phase`
+    pub phases: Phases,
+}
+
+/// An input file that is only read once it's needed
+struct InputFile {
+    path: String,
+    content: OnceLock<Result<Arc<PuzzleInput>, String>>,
+}
+
+impl InputFile {
+    fn new(path: String) -> Self {
+        Self { path, content: OnceLock::new() }
+    }
+
+    fn loaded(input: &CustomInput) -> Self {
+        Self { path: input.name.clone(), content: OnceLock::from(Ok(Arc::clone(&input.content))) }
+    }
+
+    fn content(&self) -> Result<&Arc<PuzzleInput>, &str> {
+        self.content
+            .get_or_init(|| read_input(&self.path).map(Arc::new).map_err(|e| format!("{}: {}", self.path, e)))
+            .as_ref()
+            .map_err(String::as_str)
+    }
+
+    fn name(&self) -> &str {
+        Path::new(&self.path).file_name().and_then(|e| e.to_str()).unwrap_or(&self.path)
+    }
+}
+
+/// Everything a part needs to run its test and actual inputs
+struct PartRun<'a, P> {
+    year: u16,
+    day: u8,
+    id: u8,
+    test_input: &'a InputFile,
+    test_params: &'a Arc<P>,
+    actual_input: &'a InputFile,
+    actual_params: &'a Arc<P>,
+    answer: Option<&'a str>,
+    input_dir: &'a Path,
+    timeout: Option<Duration>,
+}
+
+/// Why a part didn't produce a result
+enum Failure {
+    Panicked(String),
+    TimedOut,
+}
+
+impl Failure {
+    fn status(&self) -> Status {
+        match self {
+            Failure::Panicked(_) => Status::Panicked,
+            Failure::TimedOut => Status::TimedOut,
+        }
+    }
+
+    fn describe(&self, duration: Duration) -> String {
+        match self {
+            Failure::Panicked(message) => format!("panicked after {:?}: {}", duration, message),
+            Failure::TimedOut => format!("timed out after {:?}", duration),
+        }
+    }
+}
+
+trait Run<R: AocResult, P> {
+    fn run_test(&self, run: &PartRun<P>, input: &InputFile, expected: R, show_name: bool) -> Record;
+    /// The test on the part's own test input, unless the examples don't apply to the part
+    fn run_main_test(&self, run: &PartRun<P>, show_name: bool) -> Record;
+    fn run_actual(&self, run: &PartRun<P>) -> Record;
+    fn run_bench(&self, run: &PartRun<P>, options: &BenchOptions) -> Record;
+    fn run_all(&self, run: &PartRun<P>, options: &RunOptions) -> Vec<Record>;
+}
+
+impl<R: AocResult + Send + 'static, P: Send + Sync + 'static> Run<R, P> for SharedPart<R, P> {
+    fn run_test(&self, run: &PartRun<P>, input: &InputFile, expected: R, show_name: bool) -> Record {
+        let name = if show_name { format!(" {}", input.name()) } else { String::new() };
+        let content = match input.content() {
+            Ok(content) => content,
+            Err(e) => return skip(run, Kind::Test, input, &format!("test{}", name), e),
+        };
+        let (actual, duration, _, phases) = solve_watched(self, content, run.test_params, run.timeout);
+        let status = match &actual {
+            Ok(actual) if is_not_implemented(actual) => Status::NotImplemented,
+            Ok(actual) if *actual == expected => Status::Passed,
+            Ok(_) => Status::Failed,
+            Err(failure) => failure.status(),
+        };
+        match (&actual, status) {
+            (_, Status::Passed) => output!("{}", format!("Part {} test{} was {} {:>10}", run.id, name, "      successful".green(), format!("{:?}", duration).purple()).bright_yellow()),
+            (Ok(actual), Status::Failed) => output!("{}", format!("Part {} test{} failed after {:?}: Expected {:?} but got {:?}", run.id, name, duration, expected, actual).red()),
+            (Err(failure), _) => output!("{}", format!("Part {} test{} {}", run.id, name, failure.describe(duration)).red()),
+            _ => output!("Part {} test{} is {}", run.id, name, "not implemented".bright_black()),
+        }
+        Record { year: run.year, day: run.day, part: run.id, kind: Kind::Test, input: input.path.clone(), answer: actual.as_ref().map(answer_string).unwrap_or_default(), expected: Some(answer_string(&expected)), status, duration, phases }
+    }
+
+    fn run_main_test(&self, run: &PartRun<P>, show_name: bool) -> Record {
+        let Some(expected) = self.expect_test() else {
+            output!("Part {} test is {}", run.id, "not applicable".bright_black());
+            return Record { year: run.year, day: run.day, part: run.id, kind: Kind::Test, input: run.test_input.path.clone(), answer: String::new(), expected: None, status: Status::NotApplicable, duration: Duration::ZERO, phases: vec![] };
+        };
+
+        self.run_test(run, run.test_input, expected, show_name)
+    }
+
+    fn run_actual(&self, run: &PartRun<P>) -> Record {
+        let content = match run.actual_input.content() {
+            Ok(content) => content,
+            Err(e) => return skip(run, Kind::Actual, run.actual_input, "actual", e),
+        };
+        let (actual, duration, usage, phases) = solve_watched(self, content, run.actual_params, run.timeout);
+        let actual = match actual {
+            Ok(actual) => actual,
+            Err(failure) => return failed(run, failure, duration),
+        };
+        let status = verify(&actual, run.answer);
+        let breakdown = Some(phase::format(&phases)).filter(|e| !e.is_empty()).map(|e| format!(" {}", e.bright_black())).unwrap_or_default();
+        let usage = usage.map(|e| format!(" {}", e.to_string().bright_black())).unwrap_or_default();
+        output!("Part {} output {:>18} {:>10} {}{}{}", run.id, format!("{:?}", actual).blue(), format!("{:?}", duration).purple(), status_string(status, run.answer), breakdown, usage);
+        Record { year: run.year, day: run.day, part: run.id, kind: Kind::Actual, input: run.actual_input.path.clone(), answer: answer_string(&actual), expected: run.answer.map(String::from), status, duration, phases }
+    }
+
+    fn run_bench(&self, run: &PartRun<P>, options: &BenchOptions) -> Record {
+        let content = match run.actual_input.content() {
+            Ok(content) => content,
+            Err(e) => return skip(run, Kind::Actual, run.actual_input, "actual", e),
+        };
+        let (part, content, params, bench) = (Arc::clone(self), Arc::clone(content), Arc::clone(run.actual_params), *options);
+        let timeout = run.timeout.map(|e| e * (options.warmup + options.iterations) as u32);
+        let (actual, durations, phases) = match watched(move || benched(|| part.solve_input(&content, &params), || {}, &bench), timeout) {
+            (Ok(result), _) => result,
+            (Err(failure), elapsed) => return failed(run, failure, elapsed),
+        };
+        let stats = Stats::new(&durations);
+        let status = verify(&actual, run.answer);
+        output!("Part {} output {:>18} {:>10} {}", run.id, format!("{:?}", actual).blue(), format!("{:?}", stats.median).purple(), status_string(status, run.answer));
+        output!("       {}", stats.to_string().bright_black());
+        if !phases.is_empty() {
+            output!("       {}", format!("mean {}", phase::format(&phases)).bright_black());
+        }
+        Record { year: run.year, day: run.day, part: run.id, kind: Kind::Actual, input: run.actual_input.path.clone(), answer: answer_string(&actual), expected: run.answer.map(String::from), status, duration: stats.median, phases }
+    }
+
+    fn run_all(&self, run: &PartRun<P>, options: &RunOptions) -> Vec<Record> {
+        let mut result = vec![];
+
+        if !options.runs_part(run.id) {
+            return result;
+        }
+
+        if options.test {
+            let additional_tests = if self.expect_test().is_some() { self.additional_tests() } else { vec![] };
+            let show_name = !additional_tests.is_empty();
+
+            result.push(self.run_main_test(run, show_name));
+
+            for (name, expected) in additional_tests {
+                let input = InputFile::new(run.input_dir.join(name).to_string_lossy().into_owned());
+                result.push(self.run_test(run, &input, expected, show_name));
+            }
+        }
+
+        if options.actual {
+            result.push(match &options.bench {
+                Some(bench) => self.run_bench(run, bench),
+                None => self.run_actual(run),
+            });
+        }
+
+        result
     }
 }
 
 pub struct EmptyPart {}
 
-const NOT_IMPLEMENTED: &str = "NOT_IMPLEMENTED";
+/// What parts that aren't implemented yet return, they are reported as not implemented instead of failing
+pub const NOT_IMPLEMENTED: &str = "NOT_IMPLEMENTED";
 
-impl Part<&str> for EmptyPart {
-    fn expect_test(&self) -> &'static str {
-        NOT_IMPLEMENTED
+impl Part<&'static str> for EmptyPart {
+    fn expect_test(&self) -> Option<&'static str> {
+        Some(NOT_IMPLEMENTED)
     }
 
-    fn solve(&self, _: &[String]) -> &'static str {
+    fn solve_input(&self, _: &PuzzleInput, _: &()) -> &'static str {
         NOT_IMPLEMENTED
     }
 }
 
-pub struct Day<R1: AocResult, R2: AocResult> {
+pub type BoxedPart<R, P = ()> = Box<dyn Part<R, P> + Send + Sync>;
+
+type SharedPart<R, P> = Arc<dyn Part<R, P> + Send + Sync>;
+
+type SharedParse = Arc<dyn Fn(&PuzzleInput) + Send + Sync>;
+
+/// Part of the records of a day's shared parse, which runs before either part
+pub const SHARED_PART: u8 = 0;
+
+/// Year of the days that don't say otherwise
+pub const DEFAULT_YEAR: u16 = 2024;
+
+/// Inputs live in `input/<year>/`, the default year may also use `input/` directly like before there were years,
+/// as long as `input/` holds such inputs and not only the directories of other years
+pub fn input_dir(year: u16) -> PathBuf {
+    let dir = Path::new("input").join(year.to_string());
+
+    if year == DEFAULT_YEAR && !dir.is_dir() && has_flat_inputs(Path::new("input")) {
+        PathBuf::from("input")
+    } else {
+        dir
+    }
+}
+
+/// Whether the directory holds inputs like `01.txt` or `01_test.txt` itself rather than in year directories
+fn has_flat_inputs(dir: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+
+    entries.filter_map(Result::ok).any(|e| {
+        let name = e.file_name();
+        let name = name.to_string_lossy();
+        name.len() > 2 && name.as_bytes()[..2].iter().all(u8::is_ascii_digit) && name.ends_with(".txt")
+    })
+}
+
+pub struct Day<R1: AocResult, R2: AocResult, P = ()> {
     id: u8,
-    test_input1: Vec<String>,
-    test_input2: Vec<String>,
-    actual_input: Vec<String>,
-    part1: Box<dyn Part<R1>>,
-    part2: Box<dyn Part<R2>>,
+    year: u16,
+    input_dir: PathBuf,
+    /// The same file for both parts unless part 2 has its own examples
+    test_input1: Arc<InputFile>,
+    test_input2: Arc<InputFile>,
+    actual_input: InputFile,
+    answers: InputFile,
+    test_params: Arc<P>,
+    actual_params: Arc<P>,
+    part1: SharedPart<R1, P>,
+    part2: SharedPart<R2, P>,
+    parse: Option<SharedParse>,
+}
+
+impl<R1: AocResult + Send + 'static, R2: AocResult + Send + 'static> Day<R1, R2> {
+    pub fn new(id: u8, part1: BoxedPart<R1>, part2: BoxedPart<R2>) -> Self {
+        Self::with_params(id, part1, part2, (), ())
+    }
 }
 
-impl<R1: AocResult + 'static, R2: AocResult + 'static> Day<R1, R2> {
-    pub fn new(id: u8, part1: Box<dyn Part<R1>>, part2: Box<dyn Part<R2>>) -> Self {
+impl<R1: AocResult + Send + 'static, R2: AocResult + Send + 'static, P: Send + Sync + 'static> Day<R1, R2, P> {
+    pub fn with_params(id: u8, part1: BoxedPart<R1, P>, part2: BoxedPart<R2, P>, test_params: P, actual_params: P) -> Self {
+        let input_dir = input_dir(DEFAULT_YEAR);
+        let (test_input1, test_input2) = Self::test_inputs(&input_dir, id);
+
         Self {
             id,
-            test_input1: Self::read_test_input(id, 1),
-            test_input2: Self::read_test_input(id, 2),
-            actual_input: read_input(format!("input/{:0>2}.txt", id).as_str()),
-            part1,
-            part2,
+            year: DEFAULT_YEAR,
+            test_input1,
+            test_input2,
+            actual_input: Self::input_file(&input_dir, format!("{:0>2}.txt", id)),
+            answers: Self::input_file(&input_dir, format!("{:0>2}_answers.txt", id)),
+            input_dir,
+            test_params: Arc::new(test_params),
+            actual_params: Arc::new(actual_params),
+            part1: Arc::from(part1),
+            part2: Arc::from(part2),
+            parse: None,
+        }
+    }
+
+    /// Parses every input into `T` once before the parts run, they get it with `PuzzleInput::parsed`.
+    /// Its time is reported as a record of its own with the part `SHARED_PART`.
+    pub fn parse<T: for<'a> From<&'a PuzzleInput> + Send + Sync + 'static>(self) -> Self {
+        Self { parse: Some(Arc::new(|input| drop(input.parsed::<T>()))), ..self }
+    }
+
+    /// Moves the day to another year, which also moves its inputs
+    pub fn year(self, year: u16) -> Self {
+        let input_dir = input_dir(year);
+        let (test_input1, test_input2) = Self::test_inputs(&input_dir, self.id);
+
+        Self {
+            year,
+            test_input1,
+            test_input2,
+            actual_input: Self::input_file(&input_dir, format!("{:0>2}.txt", self.id)),
+            answers: Self::input_file(&input_dir, format!("{:0>2}_answers.txt", self.id)),
+            input_dir,
+            ..self
+        }
+    }
+
+    fn input_file(dir: &Path, name: String) -> InputFile {
+        InputFile::new(dir.join(name).to_string_lossy().into_owned())
+    }
+
+    /// Both parts share the test input if it's the same file, so it's only read and parsed once
+    fn test_inputs(dir: &Path, id: u8) -> (Arc<InputFile>, Arc<InputFile>) {
+        let test_input1 = Arc::new(Self::read_test_input(dir, id, 1));
+        let test_input2 = Self::read_test_input(dir, id, 2);
+
+        if test_input2.path == test_input1.path {
+            (Arc::clone(&test_input1), test_input1)
+        } else {
+            (test_input1, Arc::new(test_input2))
         }
     }
 
-    fn read_test_input(id: u8, test_index: usize) -> Vec<String> {
-        let test_input_name_with_id = Self::get_test_input_file_name(id, Some(test_index));
+    fn read_test_input(dir: &Path, id: u8, test_index: usize) -> InputFile {
+        let test_input_with_id = Self::input_file(dir, Self::get_test_input_file_name(id, Some(test_index)));
 
-        if Path::new(&test_input_name_with_id).is_file() {
-            read_input(&test_input_name_with_id)
+        if Path::new(&test_input_with_id.path).is_file() {
+            test_input_with_id
         } else {
-            read_input(&Self::get_test_input_file_name(id, None))
+            Self::input_file(dir, Self::get_test_input_file_name(id, None))
         }
     }
 
+    /// Known answer for the actual input, the answers file has one line per part, empty if unknown
+    fn answer(&self, part: u8) -> Option<&str> {
+        self.answers
+            .content()
+            .ok()?
+            .lines()
+            .nth(part as usize - 1)
+            .map(|e| e.trim())
+            .filter(|e| !e.is_empty())
+    }
+
     fn get_test_input_file_name(id: u8, test_id: Option<usize>) -> String {
-        format!("input/{:0>2}_test{}.txt", id, test_id.map(|i| i.to_string()).unwrap_or("".to_string()))
+        format!("{:0>2}_test{}.txt", id, test_id.map(|i| i.to_string()).unwrap_or("".to_string()))
+    }
+
+    fn test_input(&self, id: u8) -> &Arc<InputFile> {
+        if id == 1 {
+            &self.test_input1
+        } else {
+            &self.test_input2
+        }
+    }
+
+    fn part_run(&self, id: u8) -> PartRun<'_, P> {
+        PartRun {
+            year: self.year,
+            day: self.id,
+            id,
+            test_input: self.test_input(id),
+            test_params: &self.test_params,
+            actual_input: &self.actual_input,
+            actual_params: &self.actual_params,
+            answer: self.answer(id),
+            input_dir: &self.input_dir,
+            timeout: None,
+        }
     }
 
-    pub fn run_part1_test(&self) -> Duration {
-        self.part1.run_test(1, &self.test_input1)
+    pub fn run_part1_test(&self) -> Record {
+        self.part1.run_main_test(&self.part_run(1), false)
     }
 
-    pub fn run_part2_test(&self) -> Duration {
-        self.part2.run_test(2, &self.test_input2)
+    pub fn run_part2_test(&self) -> Record {
+        self.part2.run_main_test(&self.part_run(2), false)
     }
 
-    pub fn run_part1_actual(&self) -> Duration {
-        self.part1.run_actual(1, &self.actual_input)
+    pub fn run_part1_actual(&self) -> Record {
+        self.part1.run_actual(&self.part_run(1))
     }
 
-    pub fn run_part2_actual(&self) -> Duration {
-        self.part2.run_actual(2, &self.actual_input)
+    pub fn run_part2_actual(&self) -> Record {
+        self.part2.run_actual(&self.part_run(2))
     }
 
-    pub fn run(&self) -> (Duration, Duration) {
-        println!("~~~~~~~~~~~{{ {} }} ~~~~~~~~~~~", format!("Day{:0>2}", self.id).yellow());
-        (
-            self.part1.run_all(1, &self.test_input1, &self.actual_input),
-            self.part2.run_all(2, &self.test_input2, &self.actual_input),
-        )
+    pub fn run(&self, options: &RunOptions) -> Vec<Record> {
+        output!("~~~~~~~~~~~{{ {} }} ~~~~~~~~~~~", format!("Day{:0>2}", self.id).yellow());
+        let custom = options.input.as_ref().map(InputFile::loaded);
+        let options = &RunOptions { test: options.test && custom.is_none(), ..options.clone() };
+        let run = |id| PartRun {
+            actual_input: custom.as_ref().unwrap_or(&self.actual_input),
+            actual_params: if custom.is_some() && options.test_params { &self.test_params } else { &self.actual_params },
+            answer: match (&options.expect, &custom) {
+                (Some(expect), _) => Some(expect.as_str()),
+                (None, Some(_)) => None,
+                (None, None) => self.answer(id),
+            },
+            timeout: options.timeout,
+            ..self.part_run(id)
+        };
+        let mut result = vec![];
+        if options.test {
+            let mut test_inputs: Vec<&Arc<InputFile>> = vec![];
+            for input in [1, 2].into_iter().filter(|&e| options.runs_part(e)).map(|e| self.test_input(e)) {
+                if !test_inputs.iter().any(|e| Arc::ptr_eq(e, input)) {
+                    test_inputs.push(input);
+                }
+            }
+            result.extend(test_inputs.into_iter().filter_map(|e| self.run_parse(Kind::Test, e, options)));
+        }
+        if options.actual {
+            result.extend(self.run_parse(Kind::Actual, run(1).actual_input, options));
+        }
+        result.extend(self.part1.run_all(&run(1), options));
+        result.extend(self.part2.run_all(&run(2), options));
+        result
+    }
+
+    /// Runs the shared parse of an input so its time isn't added to whichever part uses it first.
+    /// When benchmarking, the parse is benchmarked on the actual input like the parts are, parsing again every iteration.
+    /// Nothing runs without a shared parse or if the input is missing, the parts report that.
+    fn run_parse(&self, kind: Kind, input: &InputFile, options: &RunOptions) -> Option<Record> {
+        let (Some(parse), Ok(content)) = (&self.parse, input.content()) else {
+            return None;
+        };
+        let label = if kind == Kind::Test { "test" } else { "actual" };
+        let (parse, content) = (Arc::clone(parse), Arc::clone(content));
+        let result = match options.bench.filter(|_| kind == Kind::Actual) {
+            Some(bench) => {
+                let timeout = options.timeout.map(|e| e * (bench.warmup + bench.iterations) as u32);
+                let (result, elapsed) = watched(move || benched(|| parse(&content), || content.clear_shared(), &bench), timeout);
+                let result = result.map(|((), durations, _)| {
+                    let stats = Stats::new(&durations);
+                    (stats.median, Some(stats), None)
+                });
+                (result, elapsed)
+            }
+            None => {
+                let (result, elapsed) = watched(move || timed(|| memory::measured(|| parse(&content))), options.timeout);
+                (result.map(|(((), usage), duration)| (duration, None, usage)), elapsed)
+            }
+        };
+        let (status, duration) = match result {
+            (Ok((duration, stats, usage)), _) => {
+                let usage = usage.map(|e| format!(" {}", e.to_string().bright_black())).unwrap_or_default();
+                output!("Shared parse {:<6} {:>23}{}", label, format!("{:?}", duration).purple(), usage);
+                if let Some(stats) = stats {
+                    output!("       {}", stats.to_string().bright_black());
+                }
+                (Status::Passed, duration)
+            }
+            (Err(failure), elapsed) => {
+                output!("{}", format!("Shared parse {} {}", label, failure.describe(elapsed)).red());
+                (failure.status(), elapsed)
+            }
+        };
+        Some(Record { year: self.year, day: self.id, part: SHARED_PART, kind, input: input.path.clone(), answer: String::new(), expected: None, status, duration, phases: vec![] })
+    }
+
+    /// Runs the examples or the actual input of a part as a test case, panicking if anything didn't pass.
+    /// Inputs aren't part of the repository, so examples are skipped without an input directory and so are unknown answers.
+    /// Skips go straight to stderr, `cargo test` would only show printed output of failed tests.
+    pub fn check(&self, part: u8, kind: Kind) {
+        let options = RunOptions { part: Some(part), test: kind == Kind::Test, actual: kind == Kind::Actual, ..RunOptions::default() };
+        // Test threads have a smaller stack than the worker threads the parts usually run on
+        let records = thread::scope(|scope| thread::Builder::new().stack_size(STACK_SIZE).spawn_scoped(scope, || self.run(&options)).expect("Failed to spawn test thread").join());
+        let records = records.unwrap_or_else(|e| panic::resume_unwind(e));
+        let has_inputs = self.input_dir.is_dir();
+
+        let mut failures = vec![];
+        let mut skips = vec![];
+
+        for record in &records {
+            match record.status {
+                Status::Passed | Status::NotApplicable => {}
+                Status::Skipped if kind == Kind::Test && !has_inputs => skips.push(format!("no input directory {}", self.input_dir.display())),
+                Status::Skipped => failures.push(format!("{} is missing", record.input)),
+                Status::Unknown => skips.push(format!("no known answer for {}", record.input)),
+                Status::NotImplemented => skips.push("not implemented".to_string()),
+                Status::Failed => failures.push(format!("{} failed: expected {}, got {}", record.input, record.expected.as_deref().unwrap_or_default(), record.answer)),
+                status => failures.push(format!("{} {}", record.input, results::status_name(status))),
+            }
+        }
+
+        skips.dedup();
+        for skip in skips {
+            let _ = writeln!(io::stderr(), "Skipped day {} of {} part {} {}: {}", self.id, self.year, part, if kind == Kind::Test { "example" } else { "actual" }, skip);
+        }
+
+        assert!(failures.is_empty(), "Day {} part {} didn't pass:\n{}", self.id, part, failures.join("\n"));
     }
 
     pub fn f(self) -> DayRunner {
-        DayRunner::new(Box::new(move || self.run()))
+        DayRunner::new(self.year, self.id, Box::new(move |options| self.run(options)))
     }
 }
 
+type RunFn = dyn Fn(&RunOptions) -> Vec<Record> + Send + Sync;
+
 pub struct DayRunner {
-    pub f: Box<dyn Fn() -> (Duration, Duration)>,
+    pub year: u16,
+    pub id: u8,
+    pub f: Box<RunFn>,
 }
 
 impl DayRunner {
-    pub fn new(f: Box<dyn Fn() -> (Duration, Duration)>) -> Self {
-        Self { f }
+    pub fn new(year: u16, id: u8, f: Box<RunFn>) -> Self {
+        Self { year, id, f }
     }
 }
 
-fn timed<R: AocResult, F: Fn() -> R>(f: F) -> (R, Duration) {
+/// Declares the day modules and a `registry()` of their runners, every module `dayNN` provides a `dayNN()` constructor.
+/// Days of another year go into their own module, e.g. `y2023/mod.rs` with `days! { 2023: day01, day02 }`,
+/// which lib declares with `mod y2023;` at the top of its `days!` so its registry is chained to the one in lib.
+/// Every part also becomes a `cargo test` case for its examples, e.g. `day07::part2_example`,
+/// and one for its known answer, e.g. `day07::part2_actual`, if `build.rs` found an answers file for the day.
+macro_rules! days {
+    (@tests $prefix:expr, $year:expr; $($day:ident),*) => {
+        #[cfg(test)]
+        mod tests {
+            $(
+                mod $day {
+                    use $crate::harness::Kind;
+
+                    fn check(part: u8, kind: Kind) {
+                        super::super::$day::$day().year($year).check(part, kind);
+                    }
+
+                    #[test]
+                    fn part1_example() {
+                        check(1, Kind::Test);
+                    }
+
+                    #[test]
+                    fn part2_example() {
+                        check(2, Kind::Test);
+                    }
+
+                    // `part1_actual` and `part2_actual`, written by the build script if the day has an answers file
+                    include!(concat!(env!("OUT_DIR"), "/actual_", $prefix, stringify!($day), ".rs"));
+                }
+            )*
+        }
+    };
+    ($year:literal: $($day:ident),* $(,)?) => {
+        $(pub mod $day;)*
+
+        /// Every registered day of the year
+        pub fn registry() -> Vec<$crate::harness::DayRunner> {
+            vec![$($day::$day().year($year).f()),*]
+        }
+
+        $crate::harness::days!(@tests concat!(stringify!($year), "_"), $year; $($day),*);
+    };
+    (@years [$($years:ident)*] mod $year:ident; $($rest:tt)*) => {
+        $crate::harness::days!(@years [$($years)* $year] $($rest)*);
+    };
+    (@years [$($year:ident)*] $($day:ident),* $(,)?) => {
+        $(pub mod $year;)*
+        $(pub mod $day;)*
+
+        /// Every registered day including those of the year modules, days are in the default year unless they say otherwise
+        pub fn registry() -> Vec<$crate::harness::DayRunner> {
+            let mut registry = vec![$($day::$day().f()),*];
+            $(registry.extend($year::registry());)*
+            registry
+        }
+
+        $crate::harness::days!(@tests "", $crate::harness::DEFAULT_YEAR; $($day),*);
+    };
+    ($($rest:tt)*) => {
+        $crate::harness::days!(@years [] $($rest)*);
+    };
+}
+
+pub(crate) use days;
+
+/// Runs the days on `jobs` worker threads, printing the output of each day in order once it's done
+pub fn run_days(days: &[&DayRunner], options: &RunOptions, jobs: usize) -> Vec<Vec<Record>> {
+    if jobs <= 1 {
+        return days.iter().map(|day| (day.f)(options)).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next = &next;
+
+            thread::Builder::new().stack_size(STACK_SIZE).spawn_scoped(scope, move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+
+                let Some(day) = days.get(index) else {
+                    break;
+                };
+
+                let (records, output) = buffered(|| (day.f)(options));
+
+                if sender.send((index, records, output)).is_err() {
+                    break;
+                }
+            })
+            .expect("Failed to spawn worker thread");
+        }
+
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut result = Vec::with_capacity(days.len());
+
+        for (index, records, output) in receiver {
+            pending.insert(index, (records, output));
+
+            while let Some((records, output)) = pending.remove(&result.len()) {
+                print!("{}", output);
+                result.push(records);
+            }
+        }
+
+        result
+    })
+}
+
+/// Solves with a timeout, also returns what the part allocated if memory usage is counted and the phases it marked
+fn solve_watched<R: AocResult + Send + 'static, P: Send + Sync + 'static>(part: &SharedPart<R, P>, content: &Arc<PuzzleInput>, params: &Arc<P>, timeout: Option<Duration>) -> (Result<R, Failure>, Duration, Option<Usage>, Phases) {
+    let (part, content, params) = (Arc::clone(part), Arc::clone(content), Arc::clone(params));
+
+    match watched(move || timed(|| phase::recorded(|| memory::measured(|| part.solve_input(&content, &params)))), timeout) {
+        (Ok((((result, usage), phases), duration)), _) => (Ok(result), duration, usage, phases),
+        (Err(failure), elapsed) => (Err(failure), elapsed, None, vec![]),
+    }
+}
+
+/// Runs `f`, on a separate thread if there's a timeout so we can stop waiting for it once the timeout has passed.
+/// A timed out thread keeps running in the background until it's done, and slows down everything measured meanwhile.
+fn watched<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(f: F, timeout: Option<Duration>) -> (Result<T, Failure>, Duration) {
+    let runaway = runaway_parts();
+    if runaway > 0 {
+        output!("{}", format!("{} timed out part(s) still running in the background, the next timing is unreliable", runaway).yellow());
+    }
+
+    let start = Instant::now();
+
+    let Some(timeout) = timeout else {
+        return (caught(f).map_err(Failure::Panicked), start.elapsed());
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    // Whoever comes second of the part finishing and the timeout passing knows whether the part ran away
+    let state = Arc::new(AtomicU8::new(RUNNING));
+    let thread_state = Arc::clone(&state);
+
+    let spawned = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let result = caught(f);
+            if thread_state.compare_exchange(RUNNING, DONE, Ordering::AcqRel, Ordering::Acquire).is_err() {
+                RUNAWAY.fetch_sub(1, Ordering::AcqRel);
+            }
+            let _ = sender.send(result);
+        });
+
+    let result = match spawned {
+        Ok(_) => match receiver.recv_timeout(timeout) {
+            Ok(result) => result.map_err(Failure::Panicked),
+            Err(RecvTimeoutError::Timeout) => {
+                // Counted before it's abandoned, so the thread can't uncount it first
+                RUNAWAY.fetch_add(1, Ordering::AcqRel);
+                if state.compare_exchange(RUNNING, ABANDONED, Ordering::AcqRel, Ordering::Acquire).is_ok() {
+                    Err(Failure::TimedOut)
+                } else {
+                    RUNAWAY.fetch_sub(1, Ordering::AcqRel);
+                    receiver.recv().unwrap_or_else(|_| Err("worker thread died".to_string())).map_err(Failure::Panicked)
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => Err(Failure::Panicked("worker thread died".to_string())),
+        },
+        Err(e) => Err(Failure::Panicked(format!("failed to spawn worker thread: {}", e))),
+    };
+
+    (result, start.elapsed())
+}
+
+/// Number of parts that timed out and are still running in the background
+pub fn runaway_parts() -> usize {
+    RUNAWAY.load(Ordering::Acquire)
+}
+
+fn timed<R, F: FnOnce() -> R>(f: F) -> (R, Duration) {
     let start = Instant::now();
     let result = f();
     (result, start.elapsed())
 }
 
-fn read_input(path: &str) -> Vec<String> {
-    fs::read_to_string(path).unwrap().split('\n').map(String::from).collect::<Vec<_>>()
+/// Returns the result of the last iteration, the duration of every iteration and the mean duration of each phase.
+/// `reset` runs untimed before every iteration, e.g. to drop what the previous one cached.
+fn benched<R: AocResult, F: Fn() -> R, S: Fn()>(f: F, reset: S, options: &BenchOptions) -> (R, Vec<Duration>, Phases) {
+    for _ in 0..options.warmup {
+        reset();
+        f();
+    }
+
+    reset();
+    let ((mut result, phases), duration) = timed(|| phase::recorded(&f));
+    let mut durations = vec![duration];
+    let mut runs = vec![phases];
+
+    for _ in 1..options.iterations {
+        reset();
+        let ((r, phases), duration) = timed(|| phase::recorded(&f));
+        result = r;
+        durations.push(duration);
+        runs.push(phases);
+    }
+
+    (result, durations, phase::mean(&runs))
+}
+
+/// Runs `f`, turning a panic into its message
+fn caught<R, F: FnOnce() -> R>(f: F) -> Result<R, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|e| e.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+fn is_not_implemented<R: AocResult>(actual: &R) -> bool {
+    answer_string(actual) == NOT_IMPLEMENTED
+}
+
+fn verify<R: AocResult>(actual: &R, answer: Option<&str>) -> Status {
+    if is_not_implemented(actual) {
+        return Status::NotImplemented;
+    }
+
+    match answer {
+        Some(answer) => match R::parse_answer(answer) {
+            Some(expected) if expected == *actual => Status::Passed,
+            _ => Status::Failed,
+        },
+        None => Status::Unknown,
+    }
+}
+
+fn status_string(status: Status, answer: Option<&str>) -> String {
+    match status {
+        Status::Passed => "correct".green().to_string(),
+        Status::Failed => format!("wrong, expected {}", answer.unwrap_or_default()).red().to_string(),
+        Status::Unknown => "unknown".bright_black().to_string(),
+        Status::Skipped => "skipped".bright_black().to_string(),
+        Status::Panicked => "panicked".red().to_string(),
+        Status::TimedOut => "timed out".red().to_string(),
+        Status::NotImplemented => "not implemented".bright_black().to_string(),
+        Status::NotApplicable => "not applicable".bright_black().to_string(),
+    }
+}
+
+/// Debug output of the answer, without the quotes around string answers
+fn answer_string<R: AocResult>(result: &R) -> String {
+    let debug = format!("{:?}", result);
+
+    match debug.strip_prefix('"').and_then(|e| e.strip_suffix('"')) {
+        Some(unquoted) => unquoted.to_string(),
+        None => debug,
+    }
+}
+
+fn skip<P>(run: &PartRun<P>, kind: Kind, input: &InputFile, label: &str, error: &str) -> Record {
+    output!("Part {} {} {} {}", run.id, label, "skipped: no input".bright_black(), error.bright_black());
+    Record { year: run.year, day: run.day, part: run.id, kind, input: input.path.clone(), answer: String::new(), expected: None, status: Status::Skipped, duration: Duration::ZERO, phases: vec![] }
+}
+
+fn failed<P>(run: &PartRun<P>, failure: Failure, duration: Duration) -> Record {
+    output!("{}", format!("Part {} actual {}", run.id, failure.describe(duration)).red());
+    Record { year: run.year, day: run.day, part: run.id, kind: Kind::Actual, input: run.actual_input.path.clone(), answer: String::new(), expected: run.answer.map(String::from), status: failure.status(), duration, phases: vec![] }
+}
+
+fn read_input(path: &str) -> Result<PuzzleInput, Error> {
+    Ok(loaded(fs::read_to_string(path)?))
+}
+
+/// Splits the lines for `LinePart`s right away, so they aren't built by whichever part is timed first
+fn loaded(text: String) -> PuzzleInput {
+    let input = PuzzleInput::new(text);
+    input.string_lines();
+    input
 }
@@ -0,0 +1,178 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// A puzzle input kept as the raw file content, the views borrow from it instead of copying
+#[derive(Debug)]
+pub struct PuzzleInput {
+    text: String,
+    /// For parts that still take `&[String]`, the harness builds it when it loads the input so no part's timing includes it
+    lines: OnceLock<Vec<String>>,
+    /// Values derived from the input, one per type, so both parts can use them
+    shared: Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl PuzzleInput {
+    /// Windows line breaks become `\n`, so every view sees the same lines
+    pub fn new(text: String) -> Self {
+        let text = if text.contains('\r') { text.replace("\r\n", "\n") } else { text };
+
+        Self { text, lines: OnceLock::new(), shared: Mutex::new(HashMap::new()) }
+    }
+
+    /// Every line without its line break, a trailing line break doesn't add an empty line
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    /// Groups of lines separated by blank lines, empty groups are skipped
+    pub fn blocks(&self) -> impl Iterator<Item = &str> {
+        self.text.split("\n\n").map(|e| e.trim_matches('\n')).filter(|e| !e.is_empty())
+    }
+
+    pub fn grid(&self) -> Grid<'_> {
+        Grid::new(&self.text)
+    }
+
+    /// Every integer in the input, a `-` directly in front of the digits makes it negative
+    pub fn ints(&self) -> Ints<'_> {
+        Ints { bytes: self.text.as_bytes(), position: 0 }
+    }
+
+    /// The lines as the old API gets them, split on every line break so a trailing one gives an empty last line
+    pub fn string_lines(&self) -> &[String] {
+        self.lines.get_or_init(|| self.text.split('\n').map(String::from).collect())
+    }
+
+    /// The input parsed into `T`, only the first call for this input parses
+    pub fn parsed<T: for<'a> From<&'a PuzzleInput> + Send + Sync + 'static>(&self) -> Arc<T> {
+        match self.shared::<T>() {
+            Some(value) => value,
+            None => self.share(T::from(self)),
+        }
+    }
+
+    /// Stores a value for later parts on this input, e.g. an intermediate result of part 1 that part 2 builds on.
+    /// Returns the stored value, which is an earlier one if there's already one of that type.
+    pub fn share<T: Send + Sync + 'static>(&self, value: T) -> Arc<T> {
+        let mut shared = self.shared.lock().unwrap_or_else(|e| e.into_inner());
+        let value = shared.entry(TypeId::of::<T>()).or_insert_with(|| Arc::new(value));
+
+        Arc::clone(value).downcast().expect("Shared value has a different type")
+    }
+
+    /// Drops every shared value, so the next `parsed` parses again
+    pub fn clear_shared(&self) {
+        self.shared.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// The value of this type shared by an earlier part, if any
+    pub fn shared<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        let shared = self.shared.lock().unwrap_or_else(|e| e.into_inner());
+
+        shared.get(&TypeId::of::<T>()).map(|e| Arc::clone(e).downcast().expect("Shared value has a different type"))
+    }
+}
+
+pub struct Ints<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Iterator for Ints<'_> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let start = self.position + self.bytes[self.position..].iter().position(u8::is_ascii_digit)?;
+        let end = start + self.bytes[start..].iter().take_while(|e| e.is_ascii_digit()).count();
+        self.position = end;
+
+        let value = self.bytes[start..end]
+            .iter()
+            .try_fold(0i64, |a, b| a.checked_mul(10)?.checked_add((b - b'0') as i64))
+            .unwrap_or_else(|| panic!("{} doesn't fit into an i64", String::from_utf8_lossy(&self.bytes[start..end])));
+
+        if start > 0 && self.bytes[start - 1] == b'-' {
+            Some(-value)
+        } else {
+            Some(value)
+        }
+    }
+}
+
+/// A rectangular grid of bytes, rows are the lines of the text
+#[derive(Debug, Copy, Clone)]
+pub struct Grid<'a> {
+    bytes: &'a [u8],
+    width: usize,
+    height: usize,
+    /// Width plus the line break
+    stride: usize,
+}
+
+impl<'a> Grid<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let bytes = text.trim_end_matches('\n').as_bytes();
+        let width = bytes.iter().position(|&e| e == b'\n').unwrap_or(bytes.len());
+        let stride = width + 1;
+        let height = if bytes.is_empty() { 0 } else { bytes.len().div_ceil(stride) };
+
+        Self { bytes, width, height, stride }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The byte at the position, None outside of the grid
+    pub fn get(&self, x: i32, y: i32) -> Option<u8> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+
+        self.bytes.get(y as usize * self.stride + x as usize).copied()
+    }
+
+    pub fn row(&self, y: usize) -> &'a [u8] {
+        let start = y * self.stride;
+        &self.bytes[start..(start + self.width).min(self.bytes.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn views_agree_on_windows_line_breaks() {
+        let unix = PuzzleInput::new("ab\ncd\n\nef\n".to_string());
+        let windows = PuzzleInput::new("ab\r\ncd\r\n\r\nef\r\n".to_string());
+
+        for input in [&unix, &windows] {
+            assert_eq!(input.lines().collect::<Vec<_>>(), ["ab", "cd", "", "ef"]);
+            assert_eq!(input.blocks().collect::<Vec<_>>(), ["ab\ncd", "ef"]);
+            assert_eq!(input.string_lines(), ["ab", "cd", "", "ef", ""]);
+        }
+
+        let grid = PuzzleInput::new("ab\r\ncd\r\n".to_string());
+        assert_eq!((grid.grid().width(), grid.grid().height()), (2, 2));
+        assert_eq!(grid.grid().row(1), b"cd");
+    }
+
+    #[test]
+    fn ints_reads_signed_numbers() {
+        let input = PuzzleInput::new("p=0,-4 v=-3,12\n9223372036854775807".to_string());
+
+        assert_eq!(input.ints().collect::<Vec<_>>(), [0, -4, -3, 12, i64::MAX]);
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't fit into an i64")]
+    fn ints_panics_instead_of_overflowing() {
+        PuzzleInput::new("99999999999999999999".to_string()).ints().count();
+    }
+}
@@ -1,102 +1,186 @@
 extern crate core;
 
 #[global_allocator]
-static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+static GLOBAL: memory::Counting<mimalloc::MiMalloc> = memory::Counting(mimalloc::MiMalloc);
 
 use colored::Colorize;
 use std::env;
-use std::io::Error;
-use std::process::Command;
+use std::io::{self, IsTerminal};
+use std::process;
 use std::time::Duration;
 
-use crate::day01::day01;
-use crate::day02::day02;
-use crate::day03::day03;
-use crate::day04::day04;
-use crate::day05::day05;
-use crate::day06::day06;
-use crate::day07::day07;
-use crate::day08::day08;
-use crate::day09::day09;
-use crate::day10::day10;
-use crate::day11::day11;
-use crate::day12::day12;
-use crate::day13::day13;
-use crate::day14::day14;
-use crate::day15::day15;
-use crate::day16::day16;
-use crate::day17::day17;
-use crate::day18::day18;
-use crate::day19::day19;
-use crate::day20::day20;
-use crate::day21::day21;
-use crate::day22::day22;
-use crate::day23::day23;
-use crate::day24::day24;
-use crate::day25::day25;
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod harness;
+use aoc_2024::harness::{self, CustomInput, Kind, Record, RunOptions, Status, DEFAULT_YEAR, SHARED_PART};
+use aoc_2024::memory;
+use aoc_2024::results::{self, Format};
+
+use crate::cli::{Command, Days};
+use crate::plot::{plot, PlotOptions};
+
+mod cli;
+mod client;
+mod fetch;
+mod plot;
+mod scaffold;
+mod submit;
+mod watch;
 
 fn main() {
-    let days = [
-        day01().f(),
-        day02().f(),
-        day03().f(),
-        day04().f(),
-        day05().f(),
-        day06().f(),
-        day07().f(),
-        day08().f(),
-        day09().f(),
-        day10().f(),
-        day11().f(),
-        day12().f(),
-        day13().f(),
-        day14().f(),
-        day15().f(),
-        day16().f(),
-        day17().f(),
-        day18().f(),
-        day19().f(),
-        day20().f(),
-        day21().f(),
-        day22().f(),
-        day23().f(),
-        day24().f(),
-        day25().f(),
-    ];
-
-    let run_one = |id: usize| (days[id - 1].f)();
-
-    let run_all = || {
-        let (p1, p2): (Vec<Duration>, Vec<Duration>) = days.iter().map(|d| (d.f)()).unzip();
-        let x = p1
+    let mut args = match cli::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e.red(), cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    if args.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+
+    // Colors would only end up as escape codes in files and pipes
+    if !io::stdout().is_terminal() && env::var_os("CLICOLOR_FORCE").is_none() {
+        colored::control::set_override(false);
+    }
+
+    if args.memory {
+        memory::enable();
+    }
+
+    let mut days = aoc_2024::registry();
+    days.sort_by_key(|e| (e.year, e.id));
+
+    if let Some(pair) = days.windows(2).find(|e| (e[0].year, e[0].id) == (e[1].year, e[1].id)) {
+        panic!("Day {} of {} is registered twice", pair[0].id, pair[0].year);
+    }
+
+    if args.command == Command::List {
+        let mut years = days.iter().map(|e| e.year).collect::<Vec<_>>();
+        years.dedup();
+
+        for year in years {
+            let ids = days.iter().filter(|e| e.year == year).map(|e| e.id as usize).collect::<Vec<_>>();
+            println!("{}: {}", year, cli::format_days(&ids));
+        }
+        return;
+    }
+
+    let year = args.year.or_else(|| days.last().map(|e| e.year)).unwrap_or(DEFAULT_YEAR);
+    days.retain(|e| e.year == year);
+    let available = days.iter().map(|e| e.id as usize).collect::<Vec<_>>();
+
+    let ids = match &args.days {
+        Days::Latest => available.last().copied().into_iter().collect(),
+        Days::All => available.clone(),
+        Days::Selected(ids) => ids.clone(),
+    };
+
+    if ids.is_empty() {
+        eprintln!("{}", format!("No days of {} are available", year).red());
+        process::exit(2);
+    }
+
+    if args.command == Command::NewDay {
+        if let Err(e) = scaffold::new_day(year, ids[0]) {
+            eprintln!("{}", e.red());
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Inputs can be fetched before the day is implemented
+    if args.command != Command::Fetch {
+        if let Some(id) = ids.iter().find(|&id| !available.contains(id)) {
+            let message = if available.is_empty() {
+                format!("No days of {} are available", year)
+            } else {
+                format!("Day {} of {} is not available, available days: {}", id, year, cli::format_days(&available))
+            };
+            eprintln!("{}", message.red());
+            process::exit(2);
+        }
+    }
+
+    let day = |id: usize| days.iter().find(|e| e.id as usize == id).unwrap();
+
+    if args.command == Command::Fetch {
+        if fetch::fetch_days(year, &ids, &args.client) > 0 {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.input.is_some() || args.stdin {
+        if ids.len() != 1 {
+            eprintln!("{}", "--input and --stdin need a single day".red());
+            process::exit(2);
+        }
+
+        let input = match &args.input {
+            Some(path) => CustomInput::from_file(path),
+            None => CustomInput::from_stdin(),
+        };
+
+        match input {
+            Ok(input) => args.options.input = Some(input),
+            Err(e) => {
+                eprintln!("{}", e.red());
+                process::exit(2);
+            }
+        }
+    }
+
+    if args.command == Command::Watch {
+        // The day runs in a fresh binary with the same arguments, minus the watch command
+        let mut forwarded = env::args().skip(1).collect::<Vec<_>>();
+        if let Some(position) = forwarded.iter().position(|e| e == "watch") {
+            forwarded.remove(position);
+        }
+        watch::watch(year, ids[0], &forwarded, args.threshold);
+    }
+
+    if args.command == Command::Submit {
+        let id = ids[0];
+        let options = RunOptions { bench: None, ..args.options.clone() };
+        let records = (day(id).f)(&options);
+        let part = options.part.unwrap_or(1);
+
+        match submit::submit(year, id, part, &records, &args.client) {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("{}", e.red());
+                process::exit(1);
+            }
+        }
+    }
+
+    let baseline = args.compare.as_ref().map(|path| {
+        results::load(path).unwrap_or_else(|e| {
+            eprintln!("{}", e.red());
+            process::exit(2);
+        })
+    });
+
+    let plain = args.format == Format::Plain;
+    harness::set_quiet(!plain);
+
+    let selected = ids.iter().map(|&id| day(id)).collect::<Vec<_>>();
+    let records = harness::run_days(&selected, &args.options, args.jobs);
+
+    let shared = records.iter().map(|e| part_duration(e, SHARED_PART)).collect::<Vec<_>>();
+    let (p1, p2): (Vec<Duration>, Vec<Duration>) = records
+        .iter()
+        .map(|e| (part_duration(e, 1), part_duration(e, 2)))
+        .unzip();
+
+    let records = records.into_iter().flatten().collect::<Vec<_>>();
+    results::print_records(&records, args.format);
+
+    if plain && ids.len() > 1 {
+        let x = shared
             .iter()
+            .chain(p1.iter())
             .chain(p2.iter())
             .map(|e| e.as_micros())
             .sum::<u128>();
@@ -107,51 +191,61 @@ fn main() {
             "                                 {:>10}",
             format!("{:?}", duration).purple()
         );
-        plot(p1, p2).unwrap();
-    };
-
-    let run_latest = || run_one(days.len());
+    }
 
-    let args = env::args().collect::<Vec<_>>();
-
-    match args.get(1) {
-        Some(arg) => {
-            if let Ok(id) = arg.parse::<usize>() {
-                run_one(id);
-            } else {
-                match arg.as_str() {
-                    "all" => {
-                        run_all();
-                    }
-                    _ => {
-                        run_latest();
-                    }
-                }
-            }
+    if let Some(path) = &args.plot {
+        let options = PlotOptions {
+            path: path.clone(),
+            log_scale: args.log_scale,
+        };
+        match plot(&ids, &shared, &p1, &p2, &options) {
+            Ok(()) if plain => println!("Plot written to {}", path.display()),
+            Ok(()) => {}
+            Err(e) => eprintln!("Failed to write plot to {}: {}", path.display(), e),
         }
-        None => {
-            run_latest();
+    }
+
+    if let Some(path) = &args.save {
+        match results::save(path, &records) {
+            Ok(()) if plain => println!("Results written to {}", path.display()),
+            Ok(()) => {}
+            Err(e) => eprintln!("Failed to write results to {}: {}", path.display(), e),
         }
-    };
+    }
+
+    let regressions = baseline.map_or(0, |baseline| results::compare(&baseline, &records, args.threshold));
+
+    if plain && ids.len() > 1 {
+        results::print_summary(&records);
+    }
+
+    let failed = records
+        .iter()
+        .filter(|e| matches!(e.status, Status::Failed | Status::Panicked | Status::TimedOut))
+        .count();
+    if failed > 0 {
+        eprintln!("{}", format!("{} part(s) failed, panicked or timed out", failed).red());
+    }
+
+    if records.iter().any(|e| e.status == Status::TimedOut) {
+        eprintln!("{}", "Timed out parts keep running until the process exits, timings of later parts are unreliable".yellow());
+    }
+
+    if regressions > 0 || failed > 0 {
+        process::exit(1);
+    }
 }
 
-fn plot(part1: Vec<Duration>, part2: Vec<Duration>) -> Result<(), Error> {
-    let convert = |v: Vec<Duration>| {
-        v.into_iter()
-            .map(|d| d.as_micros())
-            .map(|n| n.to_string())
-            .collect::<Vec<_>>()
-            .join("#")
+/// Duration of the actual run of a part or the shared parse, or of the test run if only that ran
+fn part_duration(records: &[Record], part: u8) -> Duration {
+    let find = |kind| {
+        records
+            .iter()
+            .find(|e| e.part == part && e.kind == kind && e.status != Status::Skipped)
     };
 
-    // Lol just plot with kotlin who's gonna stop me???
-    Command::new("C:\\Users\\Marvin\\.gradle\\jdks\\eclipse_adoptium-18-amd64-windows\\jdk-18.0.2.1+1\\bin\\java.exe")
-        .arg("-jar")
-        .arg("plotter.jar")
-        .arg(convert(part1))
-        .arg(convert(part2))
-        .spawn()?
-        .wait()?;
-
-    Ok(())
+    find(Kind::Actual)
+        .or_else(|| find(Kind::Test))
+        .map(|e| e.duration)
+        .unwrap_or(Duration::ZERO)
 }
@@ -0,0 +1,131 @@
+use std::alloc::{GlobalAlloc, Layout};
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Counting is off until enabled, so the wrapper only costs a relaxed load per allocation otherwise
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Counted per thread, so parts running in parallel don't see each other's allocations
+    static COUNTS: Cell<Counts> = const { Cell::new(Counts { allocations: 0, bytes: 0, live: 0, peak: 0 }) };
+}
+
+#[derive(Copy, Clone)]
+struct Counts {
+    allocations: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+/// Allocations made by a part
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Usage {
+    pub allocations: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs {} total {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+/// Wraps an allocator to count the allocations, bytes and peak live bytes of the current thread
+pub struct Counting<A>(pub A);
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Counting<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.0.alloc(layout);
+        if !ptr.is_null() {
+            record(1, layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout);
+        record(0, -(layout.size() as i64));
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.0.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(1, layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.0.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(1, new_size as i64 - layout.size() as i64);
+        }
+        new_ptr
+    }
+}
+
+fn record(allocations: u64, change: i64) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    // Fails while the thread is shutting down, those allocations don't belong to a part anyway
+    let _ = COUNTS.try_with(|counts| {
+        let mut current = counts.get();
+        current.allocations += allocations;
+        current.bytes += change.max(0) as u64;
+        current.live += change;
+        current.peak = current.peak.max(current.live);
+        counts.set(current);
+    });
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Runs `f` and returns what it allocated on this thread, if counting is enabled
+pub fn measured<R, F: FnOnce() -> R>(f: F) -> (R, Option<Usage>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return (f(), None);
+    }
+
+    COUNTS.set(Counts { allocations: 0, bytes: 0, live: 0, peak: 0 });
+    let result = f();
+    let counts = COUNTS.get();
+
+    let usage = Usage {
+        allocations: counts.allocations,
+        bytes: counts.bytes,
+        peak: counts.peak.max(0) as u64,
+    };
+
+    (result, Some(usage))
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
@@ -0,0 +1,67 @@
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    /// Start of every phase of the part running on this thread, None while no part is running
+    static MARKS: RefCell<Option<Vec<(&'static str, Instant)>>> = const { RefCell::new(None) };
+}
+
+/// Named durations within a part, in the order they first started
+pub type Phases = Vec<(String, Duration)>;
+
+/// Starts a named phase of the running part, the previous phase ends here.
+/// Time before the first phase isn't attributed to any phase, does nothing outside of the harness.
+pub fn phase(name: &'static str) {
+    let now = Instant::now();
+
+    MARKS.with_borrow_mut(|marks| {
+        if let Some(marks) = marks {
+            marks.push((name, now));
+        }
+    });
+}
+
+/// Runs `f` and returns the phases it marked, phases with the same name are added up
+pub fn recorded<R, F: FnOnce() -> R>(f: F) -> (R, Phases) {
+    // Allocated up front so marking doesn't show up in the memory usage of the part
+    MARKS.set(Some(Vec::with_capacity(16)));
+    let result = f();
+    let end = Instant::now();
+    let marks = MARKS.take().unwrap_or_default();
+
+    let mut phases: Phases = vec![];
+
+    for (i, (name, start)) in marks.iter().enumerate() {
+        let duration = marks.get(i + 1).map_or(end, |e| e.1) - *start;
+
+        match phases.iter_mut().find(|e| e.0 == *name) {
+            Some(phase) => phase.1 += duration,
+            None => phases.push((name.to_string(), duration)),
+        }
+    }
+
+    (result, phases)
+}
+
+/// Mean duration of every phase over several runs of a part
+pub fn mean(runs: &[Phases]) -> Phases {
+    let mut phases: Phases = vec![];
+
+    for (name, duration) in runs.iter().flatten() {
+        match phases.iter_mut().find(|e| e.0 == *name) {
+            Some(phase) => phase.1 += *duration,
+            None => phases.push((name.clone(), *duration)),
+        }
+    }
+
+    for phase in &mut phases {
+        phase.1 /= runs.len().max(1) as u32;
+    }
+
+    phases
+}
+
+/// "parse 1.2ms solve 3.4ms", empty if the part didn't mark any phases
+pub fn format(phases: &Phases) -> String {
+    phases.iter().map(|(name, duration)| format!("{} {:?}", name, duration)).collect::<Vec<_>>().join(" ")
+}
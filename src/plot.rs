@@ -0,0 +1,322 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Error;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const WIDTH: f64 = 1200.0;
+const HEIGHT: f64 = 600.0;
+const MARGIN_LEFT: f64 = 80.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_TOP: f64 = 50.0;
+const MARGIN_BOTTOM: f64 = 50.0;
+
+const BACKGROUND_COLOR: &str = "#ffffff";
+const AXIS_COLOR: &str = "#000000";
+const GRID_COLOR: &str = "#dddddd";
+const SHARED_COLOR: &str = "#76b7b2";
+const PART1_COLOR: &str = "#4e79a7";
+const PART2_COLOR: &str = "#f28e2b";
+const TOTAL_COLOR: &str = "#e15759";
+/// The generic `monospace` alone maps to Courier New when rasterizing, which most Linux systems don't have
+const FONT_FAMILY: &str = "Consolas, Menlo, 'DejaVu Sans Mono', 'Liberation Mono', monospace";
+
+pub struct PlotOptions {
+    /// Written as PNG if the extension is `png`, as SVG otherwise
+    pub path: PathBuf,
+    pub log_scale: bool,
+}
+
+pub fn plot(days: &[usize], shared: &[Duration], part1: &[Duration], part2: &[Duration], options: &PlotOptions) -> Result<(), Error> {
+    let svg = render_svg(&chart(days, shared, part1, part2, options.log_scale));
+
+    if options.path.extension().is_some_and(|e| e.eq_ignore_ascii_case("png")) {
+        fs::write(&options.path, render_png(&svg)?)
+    } else {
+        fs::write(&options.path, svg)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Anchor {
+    Start,
+    Middle,
+    End,
+}
+
+enum Shape {
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: &'static str,
+        title: Option<String>,
+    },
+    /// A straight line with two points, a polyline with more
+    Line {
+        points: Vec<(f64, f64)>,
+        color: &'static str,
+        width: f64,
+    },
+    /// `y` is the baseline like in SVG
+    Text {
+        x: f64,
+        y: f64,
+        anchor: Anchor,
+        size: f64,
+        text: String,
+    },
+}
+
+/// The shared parse only gets bars if any day has one
+fn chart(days: &[usize], shared: &[Duration], part1: &[Duration], part2: &[Duration], log_scale: bool) -> Vec<Shape> {
+    let micros = |v: &[Duration]| v.iter().map(|d| d.as_micros() as f64).collect::<Vec<_>>();
+    let shared = micros(shared);
+    let part1 = micros(part1);
+    let part2 = micros(part2);
+    let totals = shared.iter().zip(&part1).zip(&part2).map(|((s, a), b)| s + a + b).collect::<Vec<_>>();
+
+    let mut series = vec![("Part 1", &part1, PART1_COLOR), ("Part 2", &part2, PART2_COLOR)];
+    if shared.iter().any(|&e| e > 0.0) {
+        series.insert(0, ("Shared", &shared, SHARED_COLOR));
+    }
+
+    let scale = Scale::new(totals.iter().copied().fold(1.0, f64::max), log_scale);
+
+    let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let slot_width = plot_width / totals.len().max(1) as f64;
+    let bar_width = slot_width * 0.7 / series.len() as f64;
+
+    let x = |i: usize| MARGIN_LEFT + slot_width * i as f64;
+    let y = |value: f64| MARGIN_TOP + plot_height * (1.0 - scale.fraction(value));
+    let line = |points: Vec<(f64, f64)>, color| Shape::Line { points, color, width: 1.0 };
+    let text = |x, y, anchor, text| Shape::Text { x, y, anchor, size: 12.0, text };
+
+    let mut shapes = vec![Shape::Rect {
+        x: 0.0,
+        y: 0.0,
+        width: WIDTH,
+        height: HEIGHT,
+        color: BACKGROUND_COLOR,
+        title: None,
+    }];
+
+    let total = Duration::from_micros(totals.iter().sum::<f64>() as u64);
+    shapes.push(Shape::Text {
+        x: WIDTH / 2.0,
+        y: 30.0,
+        anchor: Anchor::Middle,
+        size: 16.0,
+        text: format!("Total {:?}{}", total, if log_scale { " (log scale)" } else { "" }),
+    });
+
+    for tick in scale.ticks() {
+        let ty = y(tick);
+        shapes.push(line(vec![(MARGIN_LEFT, ty), (WIDTH - MARGIN_RIGHT, ty)], GRID_COLOR));
+        shapes.push(text(MARGIN_LEFT - 6.0, ty + 4.0, Anchor::End, format_micros(tick)));
+    }
+
+    for i in 0..totals.len() {
+        let left = x(i) + (slot_width - series.len() as f64 * bar_width) / 2.0;
+
+        for (j, &(_, values, color)) in series.iter().enumerate() {
+            let value = values[i];
+            let top = y(value);
+            shapes.push(Shape::Rect {
+                x: left + j as f64 * bar_width,
+                y: top,
+                width: bar_width,
+                height: y(scale.min) - top,
+                color,
+                title: Some(format_micros(value)),
+            });
+        }
+
+        shapes.push(text(
+            x(i) + slot_width / 2.0,
+            HEIGHT - MARGIN_BOTTOM + 18.0,
+            Anchor::Middle,
+            format!("{:0>2}", days[i]),
+        ));
+    }
+
+    shapes.push(Shape::Line {
+        points: totals
+            .iter()
+            .enumerate()
+            .map(|(i, &total)| (x(i) + slot_width / 2.0, y(total)))
+            .collect(),
+        color: TOTAL_COLOR,
+        width: 2.0,
+    });
+
+    shapes.push(line(vec![(MARGIN_LEFT, MARGIN_TOP), (MARGIN_LEFT, HEIGHT - MARGIN_BOTTOM)], AXIS_COLOR));
+    shapes.push(line(
+        vec![(MARGIN_LEFT, HEIGHT - MARGIN_BOTTOM), (WIDTH - MARGIN_RIGHT, HEIGHT - MARGIN_BOTTOM)],
+        AXIS_COLOR,
+    ));
+
+    let legend = series.iter().map(|&(label, _, color)| (label, color)).chain([("Total", TOTAL_COLOR)]);
+
+    for (i, (label, color)) in legend.enumerate() {
+        let lx = WIDTH - MARGIN_RIGHT - 90.0;
+        let ly = MARGIN_TOP + 10.0 + i as f64 * 18.0;
+        shapes.push(Shape::Rect {
+            x: lx,
+            y: ly - 10.0,
+            width: 12.0,
+            height: 12.0,
+            color,
+            title: None,
+        });
+        shapes.push(text(lx + 18.0, ly, Anchor::Start, label.to_string()));
+    }
+
+    shapes
+}
+
+fn render_svg(shapes: &[Shape]) -> String {
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="{FONT_FAMILY}" font-size="12">"#
+    )
+    .unwrap();
+
+    for shape in shapes {
+        match shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                color,
+                title,
+            } => {
+                let title = title.as_ref().map(|e| format!("<title>{}</title>", e)).unwrap_or_default();
+                writeln!(
+                    svg,
+                    r#"<rect x="{x:.1}" y="{y:.1}" width="{width:.1}" height="{height:.1}" fill="{color}">{title}</rect>"#
+                )
+                .unwrap();
+            }
+            Shape::Line { points, color, width } => {
+                let points = points
+                    .iter()
+                    .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                writeln!(
+                    svg,
+                    r#"<polyline points="{points}" fill="none" stroke="{color}" stroke-width="{width}"/>"#
+                )
+                .unwrap();
+            }
+            Shape::Text {
+                x,
+                y,
+                anchor,
+                size,
+                text,
+            } => {
+                let anchor = match anchor {
+                    Anchor::Start => "start",
+                    Anchor::Middle => "middle",
+                    Anchor::End => "end",
+                };
+                writeln!(
+                    svg,
+                    r#"<text x="{x:.1}" y="{y:.1}" text-anchor="{anchor}" font-size="{size}">{text}</text>"#
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Rasterizes the SVG, the text is drawn with the first of the `FONT_FAMILY` fonts the system has
+fn render_png(svg: &str) -> Result<Vec<u8>, Error> {
+    let mut options = resvg::usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+
+    let tree = resvg::usvg::Tree::from_str(svg, &options).map_err(Error::other)?;
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(WIDTH as u32, HEIGHT as u32).ok_or_else(|| Error::other("empty plot"))?;
+    resvg::render(&tree, resvg::tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    pixmap.encode_png().map_err(Error::other)
+}
+
+struct Scale {
+    min: f64,
+    max: f64,
+    log: bool,
+}
+
+impl Scale {
+    fn new(max: f64, log: bool) -> Self {
+        if log {
+            Self {
+                min: 1.0,
+                max: 10f64.powf(max.log10().ceil().max(1.0)),
+                log,
+            }
+        } else {
+            let step = nice_step(max);
+            Self {
+                min: 0.0,
+                max: (max / step).ceil() * step,
+                log,
+            }
+        }
+    }
+
+    fn fraction(&self, value: f64) -> f64 {
+        let fraction = if self.log {
+            (value.max(self.min).log10() - self.min.log10()) / (self.max.log10() - self.min.log10())
+        } else {
+            (value - self.min) / (self.max - self.min)
+        };
+
+        fraction.clamp(0.0, 1.0)
+    }
+
+    fn ticks(&self) -> Vec<f64> {
+        if self.log {
+            (0..=self.max.log10() as i32).map(|e| 10f64.powi(e)).collect()
+        } else {
+            let step = nice_step(self.max);
+            (0..=(self.max / step).round() as usize)
+                .map(|i| i as f64 * step)
+                .collect()
+        }
+    }
+}
+
+fn nice_step(max: f64) -> f64 {
+    let raw = max / 5.0;
+    let magnitude = 10f64.powf(raw.log10().floor());
+
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|e| e * magnitude)
+        .find(|&e| e >= raw)
+        .unwrap()
+}
+
+/// At most two decimals, without trailing zeros
+fn format_micros(micros: f64) -> String {
+    let (value, unit) = match micros {
+        e if e >= 1_000_000.0 => (e / 1_000_000.0, "s"),
+        e if e >= 1_000.0 => (e / 1_000.0, "ms"),
+        e => (e, "µs"),
+    };
+    let value = format!("{:.2}", value);
+
+    format!("{}{}", value.trim_end_matches('0').trim_end_matches('.'), unit)
+}
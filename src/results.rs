@@ -0,0 +1,455 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+
+use crate::harness::{Kind, Record, Status, DEFAULT_YEAR, SHARED_PART};
+use crate::phase::Phases;
+
+const RECORD_HEADER: &str = "year,day,part,kind,input,answer,expected,status,duration_ns,phases";
+const HEADER: &str = "year,day,part,kind,input,answer,expected,status,duration_ns,phases,commit,timestamp";
+
+/// How the records of a run are printed, plain is the colored output of the harness
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    Plain,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone)]
+pub struct StoredRecord {
+    pub record: Record,
+    pub commit: String,
+    pub timestamp: u64,
+}
+
+pub fn save(path: &Path, records: &[Record]) -> Result<(), Error> {
+    let commit = git_commit();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|e| e.as_secs())
+        .unwrap_or(0);
+
+    let mut content = String::from(HEADER);
+    content.push('\n');
+
+    for record in records {
+        let mut fields = record_fields(record).to_vec();
+        fields.push(commit.clone());
+        fields.push(timestamp.to_string());
+
+        content.push_str(&fields.iter().map(|e| csv_escape(e)).collect::<Vec<_>>().join(","));
+        content.push('\n');
+    }
+
+    fs::write(path, content)
+}
+
+/// Prints one record per part execution for scripts, plain output is already printed while running
+pub fn print_records(records: &[Record], format: Format) {
+    match format {
+        Format::Plain => {}
+        Format::Csv => {
+            println!("{}", RECORD_HEADER);
+            for record in records {
+                println!("{}", record_fields(record).iter().map(|e| csv_escape(e)).collect::<Vec<_>>().join(","));
+            }
+        }
+        Format::Json => {
+            println!("[");
+            for (i, record) in records.iter().enumerate() {
+                println!(
+                    r#"  {{"year": {}, "day": {}, "part": {}, "kind": {}, "input": {}, "answer": {}, "expected": {}, "status": {}, "duration_ns": {}, "phases": {{{}}}}}{}"#,
+                    record.year,
+                    record.day,
+                    record.part,
+                    json_string(kind_name(record.kind)),
+                    json_string(&record.input),
+                    json_string(&record.answer),
+                    record.expected.as_deref().map_or("null".to_string(), json_string),
+                    json_string(status_name(record.status)),
+                    record.duration.as_nanos(),
+                    record.phases.iter().map(|(name, duration)| format!("{}: {}", json_string(name), duration.as_nanos())).collect::<Vec<_>>().join(", "),
+                    if i + 1 < records.len() { "," } else { "" },
+                );
+            }
+            println!("]");
+        }
+    }
+}
+
+fn record_fields(record: &Record) -> [String; 10] {
+    [
+        record.year.to_string(),
+        record.day.to_string(),
+        record.part.to_string(),
+        kind_name(record.kind).to_string(),
+        record.input.clone(),
+        record.answer.clone(),
+        record.expected.clone().unwrap_or_default(),
+        status_name(record.status).to_string(),
+        record.duration.as_nanos().to_string(),
+        format_phases(&record.phases),
+    ]
+}
+
+/// "parse=1200;solve=3400" in nanoseconds
+fn format_phases(phases: &Phases) -> String {
+    phases.iter().map(|(name, duration)| format!("{}={}", name, duration.as_nanos())).collect::<Vec<_>>().join(";")
+}
+
+fn parse_phases(value: &str) -> Option<Phases> {
+    value
+        .split(';')
+        .filter(|e| !e.is_empty())
+        .map(|e| {
+            let (name, nanos) = e.rsplit_once('=')?;
+            Some((name.to_string(), Duration::from_nanos(nanos.parse().ok()?)))
+        })
+        .collect()
+}
+
+pub fn load(path: &Path) -> Result<Vec<StoredRecord>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut records = csv_records(&content).into_iter();
+    let header = records.next().unwrap_or_default();
+
+    records
+        .enumerate()
+        .map(|(i, fields)| parse_record(&header, &fields).ok_or_else(|| format!("Malformed record {} in {}", i + 1, path.display())))
+        .collect()
+}
+
+/// Looks the fields up by the header, so files saved before year, input, expected, status or phases were recorded still load,
+/// records without a year are of the default year
+fn parse_record(header: &[String], fields: &[String]) -> Option<StoredRecord> {
+    if fields.len() != header.len() {
+        return None;
+    }
+
+    let field = |name: &str| header.iter().position(|e| e == name).map(|i| fields[i].as_str());
+
+    Some(StoredRecord {
+        record: Record {
+            year: field("year").map_or(Some(DEFAULT_YEAR), |e| e.parse().ok())?,
+            day: field("day")?.parse().ok()?,
+            part: field("part")?.parse().ok()?,
+            kind: parse_kind(field("kind")?)?,
+            input: field("input").unwrap_or_default().to_string(),
+            answer: field("answer")?.to_string(),
+            expected: field("expected").filter(|e| !e.is_empty()).map(String::from),
+            status: field("status").map_or(Some(Status::Unknown), parse_status)?,
+            duration: Duration::from_nanos(field("duration_ns")?.parse().ok()?),
+            phases: parse_phases(field("phases").unwrap_or_default())?,
+        },
+        commit: field("commit")?.to_string(),
+        timestamp: field("timestamp")?.parse().ok()?,
+    })
+}
+
+/// Prints the difference to the baseline and returns the number of regressions
+pub fn compare(baseline: &[StoredRecord], current: &[Record], threshold_percent: f64) -> usize {
+    let baseline = baseline
+        .iter()
+        .map(|e| ((e.record.year, e.record.day, e.record.part, e.record.kind), e))
+        .collect::<HashMap<_, _>>();
+
+    let mut regressions = 0;
+
+    println!("~~~~~~~~~~~{{ {} }} ~~~~~~~~~~~", "Compare".yellow());
+
+    if let Some(stored) = baseline.values().next() {
+        println!("{}", format!("Baseline from commit {} at {}", stored.commit, stored.timestamp).bright_black());
+    }
+
+    for record in current.iter().filter(|e| e.kind == Kind::Actual && e.status != Status::Skipped) {
+        let label = match record.part {
+            SHARED_PART => format!("Day{:0>2} Shared", record.day),
+            part => format!("Day{:0>2} Part {}", record.day, part),
+        };
+
+        let Some(stored) = baseline.get(&(record.year, record.day, record.part, record.kind)) else {
+            println!("{} {:>12} {}", label, format!("{:?}", record.duration).purple(), "not in baseline".bright_black());
+            continue;
+        };
+
+        let before = stored.record.duration.as_nanos().max(1) as f64;
+        let after = record.duration.as_nanos() as f64;
+        let change = (after / before - 1.0) * 100.0;
+
+        let verdict = if change > threshold_percent {
+            regressions += 1;
+            "SLOWER".red()
+        } else if change < -threshold_percent {
+            "faster".green()
+        } else {
+            "ok".normal()
+        };
+
+        println!(
+            "{} {:>12} -> {:>12} {:>+8.1}% {}",
+            label,
+            format!("{:?}", stored.record.duration),
+            format!("{:?}", record.duration).purple(),
+            change,
+            verdict,
+        );
+
+        if stored.record.answer != record.answer {
+            println!("{}", format!("    answer changed from {} to {}", stored.record.answer, record.answer).yellow());
+        }
+    }
+
+    if regressions > 0 {
+        println!("{}", format!("{} part(s) regressed by more than {}%", regressions, threshold_percent).red());
+    }
+
+    regressions
+}
+
+pub fn print_summary(records: &[Record]) {
+    let mut keys = records.iter().map(|e| (e.day, e.part)).collect::<Vec<_>>();
+    keys.dedup();
+
+    println!("~~~~~~~~~~~{{ {} }} ~~~~~~~~~~~", "Summary".yellow());
+    println!("{}", format!("{:<6}{:<8}{:<17}{:<17}{:>12}", "Day", "Part", "Test", "Actual", "Time").bright_black());
+
+    for (day, part) in keys {
+        let part_records = records.iter().filter(|e| e.day == day && e.part == part);
+        let status = |kind| {
+            part_records
+                .clone()
+                .filter(|e| e.kind == kind)
+                .map(|e| e.status)
+                .max_by_key(|&e| severity(e))
+        };
+        let duration = part_records.clone().map(|e| e.duration).sum::<Duration>();
+
+        println!(
+            "{:<6}{:<8}{}{}{:>12}",
+            format!("{:0>2}", day),
+            part_name(part),
+            colored_status(status(Kind::Test), 17),
+            colored_status(status(Kind::Actual), 17),
+            format!("{:?}", duration).purple(),
+        );
+    }
+
+    let mut counts = records.iter().fold(HashMap::new(), |mut acc, e| {
+        *acc.entry(status_name(e.status)).or_insert(0) += 1;
+        acc
+    });
+    let mut counts = counts.drain().collect::<Vec<_>>();
+    counts.sort();
+
+    println!(
+        "{}",
+        counts
+            .iter()
+            .map(|(name, count)| format!("{} {}", count, name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+}
+
+fn colored_status(status: Option<Status>, width: usize) -> String {
+    let Some(status) = status else {
+        return format!("{:<width$}", "-");
+    };
+
+    let text = format!("{:<width$}", status_name(status));
+
+    match status {
+        Status::Passed => text.green().to_string(),
+        Status::Failed | Status::Panicked | Status::TimedOut => text.red().to_string(),
+        Status::Unknown | Status::Skipped | Status::NotImplemented | Status::NotApplicable => text.bright_black().to_string(),
+    }
+}
+
+/// Which status to show if a part has several results of the same kind
+fn severity(status: Status) -> u8 {
+    match status {
+        Status::Passed => 0,
+        Status::NotApplicable => 1,
+        Status::Unknown => 2,
+        Status::Skipped => 3,
+        Status::NotImplemented => 4,
+        Status::Failed => 5,
+        Status::TimedOut => 6,
+        Status::Panicked => 7,
+    }
+}
+
+/// The number of the part, or "shared" for the shared parse of a day
+pub fn part_name(part: u8) -> String {
+    if part == SHARED_PART {
+        "shared".to_string()
+    } else {
+        part.to_string()
+    }
+}
+
+fn kind_name(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Test => "test",
+        Kind::Actual => "actual",
+    }
+}
+
+fn parse_kind(value: &str) -> Option<Kind> {
+    match value {
+        "test" => Some(Kind::Test),
+        "actual" => Some(Kind::Actual),
+        _ => None,
+    }
+}
+
+pub fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Passed => "passed",
+        Status::Failed => "failed",
+        Status::Unknown => "unknown",
+        Status::Skipped => "skipped",
+        Status::Panicked => "panicked",
+        Status::TimedOut => "timed out",
+        Status::NotImplemented => "not implemented",
+        Status::NotApplicable => "not applicable",
+    }
+}
+
+fn parse_status(value: &str) -> Option<Status> {
+    match value {
+        "passed" => Some(Status::Passed),
+        "failed" => Some(Status::Failed),
+        "unknown" => Some(Status::Unknown),
+        "skipped" => Some(Status::Skipped),
+        "panicked" => Some(Status::Panicked),
+        "timed out" => Some(Status::TimedOut),
+        "not implemented" => Some(Status::NotImplemented),
+        "not applicable" => Some(Status::NotApplicable),
+        _ => None,
+    }
+}
+
+fn git_commit() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|e| e.status.success())
+        .map(|e| String::from_utf8_lossy(&e.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn json_string(value: &str) -> String {
+    let mut result = String::from('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+pub fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Every record of a CSV file, quoted fields may contain line breaks, blank lines are skipped
+pub fn csv_records(content: &str) -> Vec<Vec<String>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(std::mem::take(&mut current)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut current));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => current.push(c),
+        }
+    }
+
+    if !current.is_empty() || !record.is_empty() {
+        record.push(current);
+        records.push(record);
+    }
+
+    records.retain(|e| e.len() > 1 || e.first().is_some_and(|e| !e.is_empty()));
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(records: &[&[&str]]) -> Vec<Vec<String>> {
+        records.iter().map(|e| e.iter().map(|e| e.to_string()).collect()).collect()
+    }
+
+    #[test]
+    fn csv_records_splits_fields_and_lines() {
+        assert_eq!(csv_records("a,b\n1,\n\n2,3"), strings(&[&["a", "b"], &["1", ""], &["2", "3"]]));
+    }
+
+    #[test]
+    fn csv_records_keeps_quoted_separators_and_quotes() {
+        assert_eq!(csv_records("\"1,2\",\"say \"\"hi\"\"\"\n"), strings(&[&["1,2", "say \"hi\""]]));
+        assert_eq!(csv_records("\"two\nlines\",x\n"), strings(&[&["two\nlines", "x"]]));
+    }
+
+    #[test]
+    fn csv_records_accepts_crlf() {
+        assert_eq!(csv_records("a,b\r\n1,2\r\n"), strings(&[&["a", "b"], &["1", "2"]]));
+    }
+
+    #[test]
+    fn csv_records_reads_what_csv_escape_writes() {
+        let fields = ["plain", "with,comma", "with \"quotes\"", "with\nbreak", ""];
+        let line = fields.iter().map(|e| csv_escape(e)).collect::<Vec<_>>().join(",");
+
+        assert_eq!(csv_records(&line), strings(&[&fields]));
+    }
+
+    #[test]
+    fn parse_record_looks_fields_up_by_header() {
+        let header = strings(&[&["commit", "timestamp", "duration_ns", "answer", "kind", "part", "day"]]).remove(0);
+        let fields = strings(&[&["abc1234", "42", "1500", "7036", "actual", "1", "16"]]).remove(0);
+
+        let stored = parse_record(&header, &fields).unwrap();
+
+        assert_eq!((stored.record.year, stored.record.day, stored.record.part, stored.record.kind), (DEFAULT_YEAR, 16, 1, Kind::Actual));
+        assert_eq!(stored.record.duration, Duration::from_nanos(1500));
+        assert_eq!(stored.record.status, Status::Unknown);
+        assert_eq!((stored.commit.as_str(), stored.timestamp), ("abc1234", 42));
+        assert!(parse_record(&header, &fields[1..]).is_none());
+    }
+}
@@ -0,0 +1,176 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+
+use colored::Colorize;
+
+use aoc_2024::harness::{input_dir, DEFAULT_YEAR};
+
+const LIB_FILE: &str = "src/lib.rs";
+const REGISTRY_START: &str = "harness::days! {";
+
+const TEMPLATE: &str = "\
+use crate::harness::Day;
+use crate::harness::LinePart;
+use crate::harness::Lines;
+use crate::harness::NOT_IMPLEMENTED;
+
+pub fn dayNN() -> Day<&'static str, &'static str> {
+    Day::new(ID, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
+}
+
+pub struct Part1;
+
+impl LinePart<&'static str> for Part1 {
+    fn expect_test(&self) -> &'static str {
+        NOT_IMPLEMENTED
+    }
+
+    fn solve(&self, input: &[String]) -> &'static str {
+        let _input = Input::from(input);
+
+        NOT_IMPLEMENTED
+    }
+}
+
+pub struct Part2;
+
+impl LinePart<&'static str> for Part2 {
+    fn expect_test(&self) -> &'static str {
+        NOT_IMPLEMENTED
+    }
+
+    fn solve(&self, input: &[String]) -> &'static str {
+        let _input = Input::from(input);
+
+        NOT_IMPLEMENTED
+    }
+}
+
+#[derive(Debug)]
+struct Input {}
+
+impl From<&[String]> for Input {
+    fn from(_value: &[String]) -> Self {
+        Self {}
+    }
+}
+";
+
+/// Creates `src/dayNN.rs` from the template, placeholder inputs and registers the day, existing files are never overwritten.
+/// Days of other years go into `src/yYYYY/dayNN.rs` and are registered in `src/yYYYY/mod.rs`, which is created if needed.
+pub fn new_day(year: u16, id: usize) -> Result<(), String> {
+    let name = format!("day{:0>2}", id);
+
+    if !Path::new(LIB_FILE).is_file() {
+        return Err(format!("{} not found, run this from the root of the project", LIB_FILE));
+    }
+
+    let source = source_file(year, id);
+    let registry = if year == DEFAULT_YEAR {
+        LIB_FILE.to_string()
+    } else {
+        let module = format!("y{}", year);
+        let registry = format!("src/{}/mod.rs", module);
+
+        fs::create_dir_all(format!("src/{}", module)).map_err(|e| format!("Failed to create src/{}: {}", module, e))?;
+
+        if create(&registry, &format!("crate::{}\n    {}:\n}}\n", REGISTRY_START, year))? {
+            println!("{} {}", "Created".green(), registry);
+        }
+        if register(LIB_FILE, &format!("mod {};", module))? {
+            println!("{} {} in {}", "Registered".green(), module, LIB_FILE);
+        }
+
+        registry
+    };
+
+    let content = TEMPLATE.replace("dayNN", &name).replace("ID", &id.to_string());
+    if !create(&source, &content)? {
+        return Err(format!("{} already exists", source));
+    }
+    println!("{} {}", "Created".green(), source);
+
+    let dir = input_dir(year);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    for path in [dir.join(format!("{:0>2}.txt", id)), dir.join(format!("{:0>2}_test.txt", id))] {
+        let path = path.to_string_lossy();
+
+        if create(&path, "")? {
+            println!("{} {}", "Created".green(), path);
+        } else {
+            println!("{} {}", "Kept".bright_black(), path);
+        }
+    }
+
+    if register(&registry, &format!("{},", name))? {
+        println!("{} {} in {}", "Registered".green(), name, registry);
+    }
+
+    println!(
+        "{}",
+        format!("Further examples go into {} as {:0>2}_test_<name>.txt and are listed in additional_tests of the part", dir.display(), id).bright_black()
+    );
+
+    Ok(())
+}
+
+/// The source of a day, days of other years than the default one are in the module of their year
+pub fn source_file(year: u16, id: usize) -> String {
+    if year == DEFAULT_YEAR {
+        format!("src/day{:0>2}.rs", id)
+    } else {
+        format!("src/y{}/day{:0>2}.rs", year, id)
+    }
+}
+
+/// Writes a new file, returns false if it already exists
+fn create(path: &str, content: &str) -> Result<bool, String> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(mut file) => {
+            file.write_all(content.as_bytes()).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+            Ok(true)
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(format!("Failed to create {}: {}", path, e)),
+    }
+}
+
+/// Adds a day or a `mod yYYYY;` line to the `days!` list in the file, keeping days and years sorted with the years first
+fn register(file: &str, entry: &str) -> Result<bool, String> {
+    let content = fs::read_to_string(file).map_err(|e| format!("Failed to read {}: {}", file, e))?;
+    let mut lines = content.lines().map(String::from).collect::<Vec<_>>();
+
+    let start = lines
+        .iter()
+        .position(|e| e.trim().ends_with(REGISTRY_START))
+        .ok_or_else(|| format!("No days! registry in {}", file))?
+        + 1;
+    let end = start + lines[start..].iter().position(|e| e.trim() == "}").ok_or_else(|| format!("Unterminated days! registry in {}", file))?;
+
+    let registered = &lines[start..end];
+
+    if registered.iter().any(|e| e.trim() == entry) {
+        return Ok(false);
+    }
+
+    let is_year = |e: &str| e.starts_with("mod ");
+    let position = if is_year(entry) {
+        let years = registered.iter().filter(|e| is_year(e.trim())).count();
+        let position = start + registered.iter().take_while(|e| is_year(e.trim()) && e.trim() < entry).count();
+        if years == 0 {
+            // Years are separated from the days by a blank line
+            lines.insert(start, String::new());
+        }
+        position
+    } else {
+        // Days come after the years and the year of the registry
+        start + registered.iter().take_while(|e| !e.trim().starts_with("day") || e.trim() < entry).count()
+    };
+    lines.insert(position, format!("    {}", entry));
+
+    fs::write(file, lines.join("\n") + "\n").map_err(|e| format!("Failed to write {}: {}", file, e))?;
+
+    Ok(true)
+}
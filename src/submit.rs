@@ -0,0 +1,342 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+use regex::Regex;
+
+use crate::client::{Client, ClientOptions};
+use aoc_2024::harness::{input_dir, Kind, Record, Status};
+use aoc_2024::results::{csv_escape, csv_records, status_name};
+
+/// Every submission ever made, so known wrong answers are never sent again
+const HISTORY_FILE: &str = "submissions.csv";
+const HEADER: &str = "day,part,answer,verdict,timestamp";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited(String),
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    /// Whether the answer itself is known to be wrong, as opposed to the attempt not counting
+    fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited(_) => "rate limited",
+            Verdict::AlreadySolved => "already solved",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "correct" => Some(Verdict::Correct),
+            "too high" => Some(Verdict::TooHigh),
+            "too low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            "rate limited" => Some(Verdict::RateLimited(String::new())),
+            "already solved" => Some(Verdict::AlreadySolved),
+            "unknown" => Some(Verdict::Unknown),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Attempt {
+    day: usize,
+    part: u8,
+    answer: String,
+    verdict: Verdict,
+}
+
+/// Submits the actual answer of a part from a run of its day, returns whether it was correct
+pub fn submit(year: u16, day: usize, part: u8, records: &[Record], options: &ClientOptions) -> Result<bool, String> {
+    let records = records.iter().filter(|e| e.part == part);
+    let failed_tests = records
+        .clone()
+        .filter(|e| e.kind == Kind::Test && !matches!(e.status, Status::Passed | Status::NotApplicable))
+        .map(|e| e.input.as_str())
+        .collect::<Vec<_>>();
+
+    if !failed_tests.is_empty() {
+        return Err(format!("The test didn't pass on {}, not submitting", failed_tests.join(", ")));
+    }
+
+    let record = records.clone().find(|e| e.kind == Kind::Actual).ok_or("The part didn't run on the actual input")?;
+
+    match record.status {
+        Status::Passed => {
+            println!("{}", "The answer is already known to be correct".green());
+            return Ok(true);
+        }
+        Status::Failed => return Err(format!("The answer doesn't match the known answer {}", record.expected.as_deref().unwrap_or_default())),
+        Status::Skipped => return Err("There's no input, fetch it first".to_string()),
+        Status::Unknown => {}
+        status => return Err(format!("Nothing to submit, the part {}", status_name(status))),
+    }
+
+    let answer = record.answer.as_str();
+    let history_file = input_dir(year).join(HISTORY_FILE);
+    let history = load_history(&history_file)?;
+
+    if let Some(attempt) = history.iter().find(|e| e.day == day && e.part == part && e.verdict == Verdict::Correct) {
+        if attempt.answer == answer {
+            println!("{}", format!("{} was already accepted", answer).green());
+            return Ok(true);
+        }
+        return Err(format!("The part was already solved with {}", attempt.answer));
+    }
+
+    if let Some(reason) = known_wrong(&history, day, part, answer) {
+        return Err(format!("Not submitting {}, {}", answer, reason));
+    }
+
+    let client = Client::new(options)?;
+    println!("Submitting {} for day {} part {}", answer.blue(), day, part);
+    let response = client.post(&format!("/{}/day/{}/answer", year, day), &[("level", &part.to_string()), ("answer", answer)])?;
+    let verdict = parse_response(&response.body);
+
+    append_history(&history_file, &Attempt {
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict: verdict.clone(),
+    })?;
+
+    match &verdict {
+        Verdict::Correct => {
+            println!("{}", "That's the right answer!".green());
+            save_answer(&input_dir(year), day, part, answer)?;
+        }
+        Verdict::RateLimited(wait) => println!("{}", format!("Rate limited, {} left to wait", wait).yellow()),
+        Verdict::AlreadySolved => println!("{}", "This part was already solved or isn't unlocked yet".yellow()),
+        Verdict::Unknown => println!("{}", format!("Unrecognized response (HTTP {})", response.status).yellow()),
+        verdict => println!("{}", format!("That's not the right answer, it's {}", verdict.name()).red()),
+    }
+
+    Ok(verdict == Verdict::Correct)
+}
+
+fn parse_response(body: &str) -> Verdict {
+    if body.contains("That's the right answer") {
+        Verdict::Correct
+    } else if body.contains("answer is too high") {
+        Verdict::TooHigh
+    } else if body.contains("answer is too low") {
+        Verdict::TooLow
+    } else if body.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if body.contains("You gave an answer too recently") {
+        let wait = Regex::new(r"You have (.+?) left to wait")
+            .unwrap()
+            .captures(body)
+            .map(|e| e[1].to_string())
+            .unwrap_or_else(|| "some time".to_string());
+        Verdict::RateLimited(wait)
+    } else if body.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown
+    }
+}
+
+/// Why the answer is wrong according to earlier attempts, numbers are also checked against too high and too low bounds
+fn known_wrong(history: &[Attempt], day: usize, part: u8, answer: &str) -> Option<String> {
+    let number = answer.parse::<i128>().ok();
+
+    history
+        .iter()
+        .filter(|e| e.day == day && e.part == part && e.verdict.is_wrong())
+        .find_map(|attempt| {
+            let bound = attempt.answer.parse::<i128>().ok();
+
+            match (&attempt.verdict, number, bound) {
+                _ if attempt.answer == answer => Some(format!("it was already submitted and was {}", attempt.verdict.name())),
+                (Verdict::TooHigh, Some(number), Some(bound)) if number >= bound => Some(format!("{} was already too high", bound)),
+                (Verdict::TooLow, Some(number), Some(bound)) if number <= bound => Some(format!("{} was already too low", bound)),
+                _ => None,
+            }
+        })
+}
+
+fn load_history(path: &Path) -> Result<Vec<Attempt>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) if !path.exists() => return Ok(vec![]),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    csv_records(&content)
+        .into_iter()
+        .skip(1)
+        .enumerate()
+        .map(|(i, fields)| parse_attempt(&fields).ok_or_else(|| format!("Malformed record {} in {}", i + 1, path.display())))
+        .collect()
+}
+
+fn parse_attempt(fields: &[String]) -> Option<Attempt> {
+    if fields.len() != 5 {
+        return None;
+    }
+
+    Some(Attempt {
+        day: fields[0].parse().ok()?,
+        part: fields[1].parse().ok()?,
+        answer: fields[2].clone(),
+        verdict: Verdict::parse(&fields[3])?,
+    })
+}
+
+fn append_history(path: &Path, attempt: &Attempt) -> Result<(), String> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|e| e.as_secs())
+        .unwrap_or(0);
+    let is_new = !path.exists();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    let mut content = String::new();
+    if is_new {
+        content.push_str(HEADER);
+        content.push('\n');
+    }
+    let fields = [
+        attempt.day.to_string(),
+        attempt.part.to_string(),
+        attempt.answer.clone(),
+        attempt.verdict.name().to_string(),
+        timestamp.to_string(),
+    ];
+    content.push_str(&fields.iter().map(|e| csv_escape(e)).collect::<Vec<_>>().join(","));
+    content.push('\n');
+
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Stores a correct answer in the answers file so later runs verify against it
+fn save_answer(dir: &Path, day: usize, part: u8, answer: &str) -> Result<(), String> {
+    let path = dir.join(format!("{:0>2}_answers.txt", day));
+
+    let mut lines = fs::read_to_string(&path)
+        .unwrap_or_default()
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    if lines.len() < part as usize {
+        lines.resize(part as usize, String::new());
+    }
+    lines[part as usize - 1] = answer.to_string();
+
+    fs::write(&path, lines.join("\n") + "\n").map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(answer: &str, verdict: Verdict) -> Attempt {
+        Attempt { day: 7, part: 2, answer: answer.to_string(), verdict }
+    }
+
+    fn record(kind: Kind, input: &str, status: Status) -> Record {
+        Record { year: 2024, day: 12, part: 2, kind, input: input.to_string(), answer: "80".to_string(), expected: None, status, duration: Default::default(), phases: vec![] }
+    }
+
+    #[test]
+    fn submit_requires_every_test_of_the_part_to_pass() {
+        let records = [
+            record(Kind::Test, "12_test.txt", Status::Passed),
+            record(Kind::Test, "12_test_small.txt", Status::Failed),
+            record(Kind::Actual, "12.txt", Status::Unknown),
+        ];
+        let options = ClientOptions { base_url: "http://127.0.0.1:9".to_string(), ..ClientOptions::default() };
+
+        let error = submit(2024, 12, 2, &records, &options).unwrap_err();
+
+        assert!(error.contains("12_test_small.txt"), "{}", error);
+        assert!(!error.contains("12_test.txt"), "{}", error);
+    }
+
+    #[test]
+    fn parse_response_recognizes_every_verdict() {
+        let verdicts = [
+            ("<p>That's the right answer! You are one gold star closer to finding the Chief Historian.</p>", Verdict::Correct),
+            ("<p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.</p>", Verdict::TooHigh),
+            ("<p>That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data.</p>", Verdict::TooLow),
+            ("<p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p>", Verdict::Wrong),
+            ("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait.</p>", Verdict::RateLimited("4m 32s".to_string())),
+            ("<p>You don't seem to be solving the right level.  Did you already complete it?</p>", Verdict::AlreadySolved),
+            ("<p>Please log in to get your puzzle input.</p>", Verdict::Unknown),
+        ];
+
+        for (body, verdict) in verdicts {
+            assert_eq!(parse_response(body), verdict, "{}", body);
+        }
+    }
+
+    #[test]
+    fn parse_response_rate_limit_without_a_wait() {
+        assert_eq!(parse_response("You gave an answer too recently"), Verdict::RateLimited("some time".to_string()));
+    }
+
+    #[test]
+    fn known_wrong_rejects_answers_that_were_already_wrong() {
+        let history = [attempt("42", Verdict::Wrong), attempt("abc", Verdict::Wrong)];
+
+        assert!(known_wrong(&history, 7, 2, "42").is_some());
+        assert!(known_wrong(&history, 7, 2, "abc").is_some());
+        assert!(known_wrong(&history, 7, 2, "43").is_none());
+    }
+
+    #[test]
+    fn known_wrong_checks_numbers_against_the_bounds() {
+        let history = [attempt("100", Verdict::TooHigh), attempt("10", Verdict::TooLow)];
+
+        assert!(known_wrong(&history, 7, 2, "100").is_some());
+        assert!(known_wrong(&history, 7, 2, "150").is_some());
+        assert!(known_wrong(&history, 7, 2, "10").is_some());
+        assert!(known_wrong(&history, 7, 2, "-5").is_some());
+        assert!(known_wrong(&history, 7, 2, "50").is_none());
+        assert!(known_wrong(&history, 7, 2, "1,2").is_none());
+    }
+
+    #[test]
+    fn known_wrong_only_uses_wrong_attempts_of_the_same_part() {
+        let history = [
+            attempt("100", Verdict::TooHigh),
+            attempt("50", Verdict::RateLimited(String::new())),
+            attempt("60", Verdict::Unknown),
+        ];
+
+        assert!(known_wrong(&history, 7, 1, "150").is_none());
+        assert!(known_wrong(&history, 8, 2, "150").is_none());
+        assert!(known_wrong(&history, 7, 2, "50").is_none());
+        assert!(known_wrong(&history, 7, 2, "60").is_none());
+    }
+}
@@ -0,0 +1,140 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use colored::Colorize;
+
+use aoc_2024::harness::input_dir;
+use aoc_2024::results::{self, StoredRecord};
+
+use crate::scaffold;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Set for the copy the watcher runs from, to the binary that cargo rebuilds
+const BUILT_EXE_VAR: &str = "AOC_WATCH_EXE";
+
+/// Modification time and size of every watched file
+type Fingerprint = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+/// Rebuilds and re-runs a day with `args` whenever its source or inputs change, comparing each run to the one before
+pub fn watch(year: u16, id: usize, args: &[String], threshold: f64) -> ! {
+    let exe = match env::var_os(BUILT_EXE_VAR) {
+        Some(exe) => PathBuf::from(exe),
+        None => run_from_copy(),
+    };
+    let results_file = env::temp_dir().join(format!("aoc-watch-{}.csv", process::id()));
+    let mut previous: Option<Vec<StoredRecord>> = None;
+    let mut last = None;
+
+    loop {
+        let current = fingerprint(year, id);
+
+        if last.as_ref() != Some(&current) {
+            if last.is_some() {
+                println!("{}", "Change detected, rebuilding".yellow());
+            }
+            // Taken before building, so changes made during the build trigger another run
+            last = Some(current);
+
+            if let Some(records) = rebuild_and_run(&exe, args, &results_file) {
+                if let Some(previous) = &previous {
+                    let current = records.iter().map(|e| e.record.clone()).collect::<Vec<_>>();
+                    results::compare(previous, &current, threshold);
+                }
+                previous = Some(records);
+            }
+
+            println!("{}", format!("Watching day {} for changes", id).bright_black());
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Windows can't replace a running binary, so the watcher runs from a copy and cargo rebuilds the original.
+/// Runs the copy with the same arguments and exits with its exit code, returns the original if it can't be copied.
+fn run_from_copy() -> PathBuf {
+    let exe = env::current_exe().unwrap_or_else(|e| {
+        eprintln!("{}", format!("Failed to find the binary: {}", e).red());
+        process::exit(1);
+    });
+    let copy = env::temp_dir().join(format!("aoc-watch-{}{}", process::id(), env::consts::EXE_SUFFIX));
+
+    if let Err(e) = fs::copy(&exe, &copy) {
+        eprintln!("{}", format!("Failed to copy the binary to {}, rebuilds may fail: {}", copy.display(), e).yellow());
+        return exe;
+    }
+
+    let status = Command::new(&copy).args(env::args_os().skip(1)).env(BUILT_EXE_VAR, &exe).status();
+    let _ = fs::remove_file(&copy);
+
+    match status {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("{}", format!("Failed to run {}: {}", copy.display(), e).red());
+            process::exit(1);
+        }
+    }
+}
+
+fn fingerprint(year: u16, id: usize) -> Fingerprint {
+    let prefix = format!("{:0>2}", id);
+    let mut paths = vec![PathBuf::from(scaffold::source_file(year, id))];
+
+    if let Ok(entries) = fs::read_dir(input_dir(year)) {
+        paths.extend(
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|e| e.extension().is_some_and(|e| e == "txt"))
+                .filter(|e| e.file_name().and_then(|e| e.to_str()).is_some_and(|e| e.starts_with(&prefix))),
+        );
+    }
+
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let metadata = fs::metadata(&path).ok();
+            let modified = metadata.as_ref().and_then(|e| e.modified().ok());
+            let len = metadata.map(|e| e.len()).unwrap_or(0);
+            (path, modified, len)
+        })
+        .collect()
+}
+
+/// Builds with cargo and runs the fresh binary, returns its results unless something failed
+fn rebuild_and_run(exe: &Path, args: &[String], results_file: &Path) -> Option<Vec<StoredRecord>> {
+    let mut build = Command::new("cargo");
+    build.args(["build", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+
+    match build.status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            eprintln!("{}", "Build failed".red());
+            return None;
+        }
+        Err(e) => {
+            eprintln!("{}", format!("Failed to run cargo: {}", e).red());
+            return None;
+        }
+    }
+
+    let _ = fs::remove_file(results_file);
+
+    // The exit code only says whether something failed, which the output already shows
+    if let Err(e) = Command::new(exe).args(args).arg("--save").arg(results_file).status() {
+        eprintln!("{}", format!("Failed to run the day: {}", e).red());
+        return None;
+    }
+
+    results::load(results_file).map_err(|e| eprintln!("{}", e.red())).ok()
+}
@@ -0,0 +1,58 @@
+use crate::harness::Day;
+use crate::harness::LinePart;
+use crate::harness::Lines;
+
+pub fn day01() -> Day<u32, u32> {
+    Day::new(1, Box::new(Lines(Part1 {})), Box::new(Lines(Part2 {})))
+}
+
+pub struct Part1;
+
+impl LinePart<u32> for Part1 {
+    fn expect_test(&self) -> u32 {
+        142
+    }
+
+    fn solve(&self, input: &[String]) -> u32 {
+        input
+            .iter()
+            .filter(|e| !e.is_empty())
+            .map(|e| calibration_value(e, &[]))
+            .sum()
+    }
+}
+
+pub struct Part2;
+
+impl LinePart<u32> for Part2 {
+    fn expect_test(&self) -> u32 {
+        281
+    }
+
+    fn solve(&self, input: &[String]) -> u32 {
+        input
+            .iter()
+            .filter(|e| !e.is_empty())
+            .map(|e| calibration_value(e, &DIGIT_NAMES))
+            .sum()
+    }
+}
+
+const DIGIT_NAMES: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// First and last digit of the line, digits may also be spelled out with one of `names`
+fn calibration_value(line: &str, names: &[&str]) -> u32 {
+    let digit_at = |i: usize| {
+        let rest = &line[i..];
+
+        rest.chars()
+            .next()
+            .and_then(|e| e.to_digit(10))
+            .or_else(|| names.iter().position(|&e| rest.starts_with(e)).map(|e| e as u32 + 1))
+    };
+
+    let first = (0..line.len()).find_map(digit_at).unwrap();
+    let last = (0..line.len()).rev().find_map(digit_at).unwrap();
+
+    first * 10 + last
+}
@@ -0,0 +1,4 @@
+crate::harness::days! {
+    2023:
+    day01,
+}